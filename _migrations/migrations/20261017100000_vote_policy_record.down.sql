@@ -0,0 +1,5 @@
+ALTER TABLE pdao_vote
+    DROP COLUMN IF EXISTS policy_version,
+    DROP COLUMN IF EXISTS policy,
+    DROP COLUMN IF EXISTS vote_counts,
+    DROP COLUMN IF EXISTS evaluation_description;
@@ -0,0 +1,5 @@
+ALTER TABLE pdao_vote
+    ADD COLUMN IF NOT EXISTS policy_version         VARCHAR(32),
+    ADD COLUMN IF NOT EXISTS policy                 TEXT,
+    ADD COLUMN IF NOT EXISTS vote_counts            TEXT,
+    ADD COLUMN IF NOT EXISTS evaluation_description TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE pdao_vote
+    ADD COLUMN IF NOT EXISTS vote_split TEXT;
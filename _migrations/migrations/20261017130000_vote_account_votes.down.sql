@@ -0,0 +1,2 @@
+ALTER TABLE pdao_vote
+    DROP COLUMN IF EXISTS account_votes;
@@ -0,0 +1 @@
+DELETE FROM pdao_network WHERE id IN (3, 4);
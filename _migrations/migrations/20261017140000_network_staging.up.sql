@@ -0,0 +1,6 @@
+INSERT INTO pdao_network(id, hash, chain, display, ss58_prefix, token_ticker, token_decimal_count)
+VALUES(3, '0xE143F23803AC50E8F6F8E62695D1CE9E4E1D68AA36C1CD2CFD15340213F3423E', 'westend', 'Westend', 42, 'WND', 12)
+ON CONFLICT(id) DO NOTHING;
+INSERT INTO pdao_network(id, hash, chain, display, ss58_prefix, token_ticker, token_decimal_count)
+VALUES(4, '0x77AFD6190F1554AD45FD0D31AEE62AACC33C6DB0EA801129ACB813F913E0764F', 'paseo', 'Paseo', 0, 'PAS', 10)
+ON CONFLICT(id) DO NOTHING;
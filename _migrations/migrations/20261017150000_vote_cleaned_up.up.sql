@@ -0,0 +1,2 @@
+ALTER TABLE pdao_vote
+    ADD COLUMN IF NOT EXISTS is_cleaned_up BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+ALTER TABLE pdao_referendum
+    DROP COLUMN IF EXISTS preimage_length,
+    DROP COLUMN IF EXISTS preimage_hash;
@@ -0,0 +1,3 @@
+ALTER TABLE pdao_referendum
+    ADD COLUMN IF NOT EXISTS preimage_hash VARCHAR(66),
+    ADD COLUMN IF NOT EXISTS preimage_length INTEGER;
@@ -0,0 +1,34 @@
+CREATE TABLE IF NOT EXISTS pdao_vote_intent
+(
+    id              SERIAL PRIMARY KEY,
+    network_id      INT NOT NULL,
+    referendum_id   INT NOT NULL,
+    index           INT NOT NULL,
+    status          VARCHAR(32) NOT NULL,
+    record          TEXT NOT NULL,
+    block_hash      VARCHAR(128),
+    block_number    BIGINT,
+    extrinsic_index INT,
+    vote_id         INT,
+    error           TEXT,
+    created_at      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    updated_at      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT pdao_vote_intent_fk_network
+        FOREIGN KEY (network_id)
+            REFERENCES pdao_network (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE,
+    CONSTRAINT pdao_vote_intent_fk_referendum
+        FOREIGN KEY (referendum_id)
+            REFERENCES pdao_referendum (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE,
+    CONSTRAINT pdao_vote_intent_fk_vote
+        FOREIGN KEY (vote_id)
+            REFERENCES pdao_vote (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS pdao_vote_intent_idx_status
+    ON pdao_vote_intent (status);
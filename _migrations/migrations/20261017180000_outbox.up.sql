@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS pdao_outbox
+(
+    id              SERIAL PRIMARY KEY,
+    idempotency_key VARCHAR(256) NOT NULL,
+    payload         TEXT NOT NULL,
+    status          VARCHAR(32) NOT NULL,
+    attempt_count   INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    last_error      TEXT,
+    created_at      TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    delivered_at    TIMESTAMP WITHOUT TIME ZONE,
+    CONSTRAINT pdao_outbox_u_idempotency_key UNIQUE (idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS pdao_outbox_idx_status_next_attempt_at
+    ON pdao_outbox (status, next_attempt_at);
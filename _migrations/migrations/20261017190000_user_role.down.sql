@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS pdao_user_role_change;
+DROP TABLE IF EXISTS pdao_user_role;
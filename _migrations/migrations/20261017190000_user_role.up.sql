@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS pdao_user_role
+(
+    id               SERIAL PRIMARY KEY,
+    telegram_user_id BIGINT NOT NULL,
+    role             VARCHAR(32) NOT NULL,
+    created_at       TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT pdao_user_role_u_user_role UNIQUE (telegram_user_id, role)
+);
+
+CREATE TABLE IF NOT EXISTS pdao_user_role_change
+(
+    id                     SERIAL PRIMARY KEY,
+    telegram_user_id       BIGINT NOT NULL,
+    role                   VARCHAR(32) NOT NULL,
+    is_granted             BOOLEAN NOT NULL,
+    actor_telegram_user_id BIGINT NOT NULL,
+    actor_username         VARCHAR(128) NOT NULL,
+    created_at             TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS pdao_user_role_change_idx_telegram_user_id
+    ON pdao_user_role_change (telegram_user_id);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS pdao_audit_log;
@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS pdao_audit_log
+(
+    id                     SERIAL PRIMARY KEY,
+    actor                  VARCHAR(128) NOT NULL,
+    actor_telegram_user_id BIGINT,
+    command                VARCHAR(64) NOT NULL,
+    arguments              TEXT NOT NULL,
+    referendum_id          INT,
+    telegram_chat_id       BIGINT,
+    telegram_thread_id     INT,
+    is_success             BOOLEAN NOT NULL,
+    error                  TEXT,
+    created_at             TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    CONSTRAINT pdao_audit_log_fk_referendum
+        FOREIGN KEY (referendum_id)
+            REFERENCES pdao_referendum (id)
+            ON DELETE RESTRICT
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS pdao_audit_log_idx_telegram_chat_id_thread_id
+    ON pdao_audit_log (telegram_chat_id, telegram_thread_id);
@@ -4,7 +4,7 @@ use std::fmt;
 const DEFAULT_CONFIG_DIR: &str = "./config";
 const DEV_CONFIG_DIR: &str = "../_config";
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 pub enum Environment {
     Development,
     Test,
@@ -49,6 +49,20 @@ pub struct SubstrateConfig {
     pub gov_proxy_seed_phrase: String,
 }
 
+#[derive(Clone, Deserialize)]
+pub struct KeystoreConfig {
+    pub file_path: String,
+    pub passphrase: String,
+}
+
+impl fmt::Debug for KeystoreConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeystoreConfig")
+            .field("file_path", &self.file_path)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct ReferendumImporterConfig {
     pub opensquare_space: String,
@@ -62,6 +76,18 @@ pub struct TelegramConfig {
     pub chat_id: i64,
     pub bot_username: String,
     pub bot_chat_thread_id: i32,
+    /// Comma-separated Telegram user ids that are admins whatever their roles in the DB, so
+    /// that they can grant the first roles.
+    pub admin_user_ids: String,
+}
+
+impl TelegramConfig {
+    pub fn get_admin_user_ids(&self) -> Vec<i64> {
+        self.admin_user_ids
+            .split(',')
+            .filter_map(|user_id| user_id.trim().parse().ok())
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -71,18 +97,91 @@ pub struct OpenAPIConfig {
     pub api_key: String,
 }
 
+/// How a voting account votes on a referendum with a reported conflict of interest.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictOfInterestBehavior {
+    Abstain,
+    Skip,
+    Vote,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct VotingAccountConfig {
+    pub address: String,
+    pub coi_behavior: ConflictOfInterestBehavior,
+}
+
+/// Voting configuration of a network other than Polkadot and Kusama, e.g. a staging network.
+#[derive(Clone, Debug, Deserialize)]
+pub struct NetworkVoterConfig {
+    pub chain: String,
+    pub real_account_address: String,
+    pub voting_accounts: Vec<VotingAccountConfig>,
+    pub proxy_account_seed_phrase: String,
+    /// Required with offline signing, when the seed phrase is not available to the voter.
+    pub proxy_account_address: Option<String>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct VoterConfig {
-    pub voting_admin_usernames: String,
     pub polkadot_real_account_address: String,
-    pub polkadot_dv_delegation_account_address: String,
+    pub polkadot_voting_accounts: Vec<VotingAccountConfig>,
     pub polkadot_proxy_account_seed_phrase: String,
+    pub polkadot_proxy_account_address: Option<String>,
     pub kusama_real_account_address: String,
-    pub kusama_dv_delegation_account_address: String,
+    pub kusama_voting_accounts: Vec<VotingAccountConfig>,
     pub kusama_proxy_account_seed_phrase: String,
+    pub kusama_proxy_account_address: Option<String>,
     pub sleep_seconds: u64,
+    pub proxy_balance_check_seconds: u64,
+    pub proxy_balance_alert_vote_count: u32,
+    pub metadata_check_seconds: u64,
+    pub reconciliation_seconds: u64,
+    pub unlock_check_seconds: u64,
+    pub offline_signing: bool,
+    pub offline_signing_dir_path: String,
+    pub offline_signing_timeout_seconds: u64,
     pub min_referendum_id: u32,
     pub voting_policy_version: String,
+    #[serde(default)]
+    pub networks: Vec<NetworkVoterConfig>,
+}
+
+impl VoterConfig {
+    /// Polkadot and Kusama, followed by the other configured networks.
+    pub fn get_network_configs(&self) -> Vec<NetworkVoterConfig> {
+        let mut chains = vec!["polkadot".to_string(), "kusama".to_string()];
+        chains.extend(self.networks.iter().map(|network| network.chain.clone()));
+        chains
+            .iter()
+            .filter_map(|chain| self.get_network_config(chain))
+            .collect()
+    }
+
+    pub fn get_network_config(&self, chain: &str) -> Option<NetworkVoterConfig> {
+        match chain {
+            "polkadot" => Some(NetworkVoterConfig {
+                chain: chain.to_string(),
+                real_account_address: self.polkadot_real_account_address.clone(),
+                voting_accounts: self.polkadot_voting_accounts.clone(),
+                proxy_account_seed_phrase: self.polkadot_proxy_account_seed_phrase.clone(),
+                proxy_account_address: self.polkadot_proxy_account_address.clone(),
+            }),
+            "kusama" => Some(NetworkVoterConfig {
+                chain: chain.to_string(),
+                real_account_address: self.kusama_real_account_address.clone(),
+                voting_accounts: self.kusama_voting_accounts.clone(),
+                proxy_account_seed_phrase: self.kusama_proxy_account_seed_phrase.clone(),
+                proxy_account_address: self.kusama_proxy_account_address.clone(),
+            }),
+            _ => self
+                .networks
+                .iter()
+                .find(|network| network.chain == chain)
+                .cloned(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -117,19 +216,39 @@ pub struct ArchiveConfig {
     pub temp_file_dir_path: String,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct OutboxConfig {
+    pub poll_seconds: u64,
+    pub lease_seconds: u64,
+    pub max_attempts: u32,
+    pub initial_backoff_seconds: u64,
+    pub max_backoff_seconds: u64,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct Config {
+    pub env: Environment,
     pub common: CommonConfig,
     pub http: HTTPConfig,
     pub log: LogConfig,
     pub postgres: PostgreSQLConfig,
     pub substrate: SubstrateConfig,
+    pub keystore: KeystoreConfig,
     pub metrics: MetricsConfig,
     pub referendum_importer: ReferendumImporterConfig,
     pub telegram: TelegramConfig,
     pub openai: OpenAPIConfig,
     pub voter: VoterConfig,
     pub archive: ArchiveConfig,
+    pub outbox: OutboxConfig,
+}
+
+pub fn get_config_dir() -> String {
+    if cfg!(debug_assertions) {
+        std::env::var("PDAO_CONFIG_DIR").unwrap_or_else(|_| DEV_CONFIG_DIR.into())
+    } else {
+        std::env::var("PDAO_CONFIG_DIR").unwrap_or_else(|_| DEFAULT_CONFIG_DIR.into())
+    }
 }
 
 impl Config {
@@ -139,11 +258,7 @@ impl Config {
                 .unwrap_or_else(|_| "Production".into())
                 .as_str(),
         );
-        let config_dir = if cfg!(debug_assertions) {
-            std::env::var("PDAO_CONFIG_DIR").unwrap_or_else(|_| DEV_CONFIG_DIR.into())
-        } else {
-            std::env::var("PDAO_CONFIG_DIR").unwrap_or_else(|_| DEFAULT_CONFIG_DIR.into())
-        };
+        let config_dir = get_config_dir();
         let config = config::Config::builder()
             .set_default("env", env.to_string())?
             .add_source(config::File::with_name(&format!("{config_dir}/base")))
@@ -157,6 +272,14 @@ impl Config {
         config.try_deserialize()
     }
 
+    pub fn get_voting_policy_file_path(&self) -> String {
+        format!(
+            "{}/policy/{}",
+            get_config_dir(),
+            self.voter.voting_policy_version,
+        )
+    }
+
     pub fn get_postgres_url(&self) -> String {
         format!(
             "postgres://{}:{}@{}:{}/{}?sslmode=disable",
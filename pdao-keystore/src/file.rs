@@ -0,0 +1,120 @@
+use crypto_secretbox::aead::rand_core::RngCore;
+use crypto_secretbox::aead::{Aead, AeadCore, KeyInit, OsRng};
+use crypto_secretbox::{Nonce, XSalsa20Poly1305};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
+
+const KEYSTORE_FILE_VERSION: u8 = 1;
+const SALT_LENGTH: usize = 32;
+// same scrypt parameters as the polkadot-js JSON keystore
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Secret URIs by key name, encrypted with a key derived from a passphrase using scrypt and
+/// sealed with XSalsa20-Poly1305.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeystoreFile {
+    version: u8,
+    scrypt_log_n: u8,
+    scrypt_r: u32,
+    scrypt_p: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn get_cipher(
+    passphrase: &str,
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> anyhow::Result<XSalsa20Poly1305> {
+    let params = scrypt::Params::new(log_n, r, p, 32)
+        .map_err(|error| anyhow::anyhow!("Invalid scrypt parameters: {error}"))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, key.as_mut())
+        .map_err(|error| anyhow::anyhow!("Cannot derive the keystore key: {error}"))?;
+    XSalsa20Poly1305::new_from_slice(key.as_ref())
+        .map_err(|error| anyhow::anyhow!("Invalid keystore key: {error}"))
+}
+
+/// Encrypts the secret URIs into the JSON content of a keystore file.
+pub fn encrypt(secrets: &BTreeMap<String, String>, passphrase: &str) -> anyhow::Result<String> {
+    let plaintext = Zeroizing::new(serde_json::to_vec(secrets)?);
+    let mut salt = [0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = get_cipher(passphrase, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+    let nonce = XSalsa20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| anyhow::anyhow!("Cannot encrypt the keystore."))?;
+    Ok(serde_json::to_string_pretty(&KeystoreFile {
+        version: KEYSTORE_FILE_VERSION,
+        scrypt_log_n: SCRYPT_LOG_N,
+        scrypt_r: SCRYPT_R,
+        scrypt_p: SCRYPT_P,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })?)
+}
+
+/// Decrypts the secret URIs from the JSON content of a keystore file.
+pub fn decrypt(
+    content: &str,
+    passphrase: &str,
+) -> anyhow::Result<BTreeMap<String, Zeroizing<String>>> {
+    let file: KeystoreFile = serde_json::from_str(content)?;
+    if file.version != KEYSTORE_FILE_VERSION {
+        anyhow::bail!("Unsupported keystore file version {}.", file.version);
+    }
+    let nonce = hex::decode(&file.nonce)?;
+    if nonce.len() != 24 {
+        anyhow::bail!("Invalid keystore nonce.");
+    }
+    let cipher = get_cipher(
+        passphrase,
+        &hex::decode(&file.salt)?,
+        file.scrypt_log_n,
+        file.scrypt_r,
+        file.scrypt_p,
+    )?;
+    let plaintext = Zeroizing::new(
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                hex::decode(&file.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| anyhow::anyhow!("Cannot decrypt the keystore, check the passphrase."))?,
+    );
+    let secrets: BTreeMap<String, String> = serde_json::from_slice(&plaintext)
+        .map_err(|_| anyhow::anyhow!("Invalid keystore content."))?;
+    Ok(secrets
+        .into_iter()
+        .map(|(name, secret)| (name, Zeroizing::new(secret)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let secrets = BTreeMap::from([
+            ("gov".to_string(), "//Alice".to_string()),
+            ("polkadot_proxy".to_string(), "//Bob".to_string()),
+        ]);
+        let content = encrypt(&secrets, "passphrase").unwrap();
+        assert!(!content.contains("//Alice"));
+        let decrypted = decrypt(&content, "passphrase").unwrap();
+        assert_eq!(decrypted.len(), 2);
+        assert_eq!(decrypted["gov"].as_str(), "//Alice");
+        assert_eq!(decrypted["polkadot_proxy"].as_str(), "//Bob");
+        assert!(decrypt(&content, "wrong passphrase").is_err());
+    }
+}
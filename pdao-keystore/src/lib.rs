@@ -0,0 +1,127 @@
+use pdao_config::Config;
+use pdao_types::substrate::account_id::AccountId;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use subxt_signer::{sr25519, SecretUri};
+
+pub mod file;
+
+/// The key that signs the OpenSquare and SubSquare requests.
+pub const GOV_KEY_NAME: &str = "gov";
+
+/// The key of the proxy account that signs the extrinsics on the chain.
+pub fn get_proxy_key_name(chain: &str) -> String {
+    format!("{chain}_proxy")
+}
+
+/// Signs with a key without exposing it.
+pub trait Signer: Send + Sync {
+    fn public_key(&self) -> [u8; 32];
+
+    fn sign(&self, message: &[u8]) -> [u8; 64];
+
+    fn account_id(&self) -> AccountId {
+        AccountId::from(self.public_key())
+    }
+}
+
+pub struct Sr25519Signer {
+    keypair: sr25519::Keypair,
+}
+
+impl Sr25519Signer {
+    pub fn from_secret_uri(secret_uri: &str) -> anyhow::Result<Self> {
+        // the errors would echo the secret, keep them out
+        let uri =
+            SecretUri::from_str(secret_uri).map_err(|_| anyhow::anyhow!("Invalid secret URI."))?;
+        let keypair =
+            sr25519::Keypair::from_uri(&uri).map_err(|_| anyhow::anyhow!("Invalid keypair."))?;
+        Ok(Self { keypair })
+    }
+}
+
+impl Signer for Sr25519Signer {
+    fn public_key(&self) -> [u8; 32] {
+        self.keypair.public_key().0
+    }
+
+    fn sign(&self, message: &[u8]) -> [u8; 64] {
+        self.keypair.sign(message).0
+    }
+}
+
+impl fmt::Debug for Sr25519Signer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Sr25519Signer({})", self.account_id())
+    }
+}
+
+/// Signing keys by name, loaded once at startup.
+pub struct Keystore {
+    signers: BTreeMap<String, Arc<dyn Signer>>,
+}
+
+impl fmt::Debug for Keystore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Keystore")
+            .field("keys", &self.signers.keys().collect::<Vec<&String>>())
+            .finish()
+    }
+}
+
+impl Keystore {
+    /// Loads the keys from the passphrase-encrypted keystore file when one is configured, and
+    /// from the seed phrases of the configuration otherwise.
+    pub fn load(config: &Config) -> anyhow::Result<Self> {
+        if config.keystore.file_path.is_empty() {
+            log::warn!("No keystore file configured, load the keys from the configuration.");
+            return Ok(Self::from_config(config));
+        }
+        let content = std::fs::read_to_string(&config.keystore.file_path)?;
+        let mut signers: BTreeMap<String, Arc<dyn Signer>> = BTreeMap::new();
+        for (name, secret_uri) in file::decrypt(&content, &config.keystore.passphrase)? {
+            let signer = Sr25519Signer::from_secret_uri(&secret_uri)
+                .map_err(|error| anyhow::anyhow!("Keystore key {name}: {error}"))?;
+            signers.insert(name, Arc::new(signer));
+        }
+        log::info!("Loaded {} keys from the keystore.", signers.len());
+        Ok(Self { signers })
+    }
+
+    /// Invalid seed phrases are skipped, so that the services start with placeholder values and
+    /// only fail when signing with them.
+    fn from_config(config: &Config) -> Self {
+        let mut secret_uris = vec![(
+            GOV_KEY_NAME.to_string(),
+            config.substrate.gov_proxy_seed_phrase.clone(),
+        )];
+        for network_config in config.voter.get_network_configs() {
+            secret_uris.push((
+                get_proxy_key_name(&network_config.chain),
+                network_config.proxy_account_seed_phrase,
+            ));
+        }
+        let mut signers: BTreeMap<String, Arc<dyn Signer>> = BTreeMap::new();
+        for (name, secret_uri) in secret_uris {
+            if secret_uri.is_empty() {
+                continue;
+            }
+            match Sr25519Signer::from_secret_uri(&secret_uri) {
+                Ok(signer) => {
+                    signers.insert(name, Arc::new(signer));
+                }
+                Err(error) => log::warn!("Skip configured key {name}: {error}"),
+            }
+        }
+        Self { signers }
+    }
+
+    pub fn get_signer(&self, name: &str) -> anyhow::Result<Arc<dyn Signer>> {
+        self.signers
+            .get(name)
+            .cloned()
+            .ok_or(anyhow::anyhow!("Key {name} not found in the keystore."))
+    }
+}
@@ -0,0 +1,65 @@
+//! Creates and inspects keystore files. The passphrase is read from the
+//! `PDAO_KEYSTORE_PASSPHRASE` environment variable, so that it doesn't end up in the shell
+//! history.
+//!
+//! ```text
+//! pdao-keystore encrypt <secrets_json_file> <keystore_file>
+//! pdao-keystore list <keystore_file>
+//! ```
+//!
+//! The secrets file is a JSON object of secret URIs by key name, e.g. `gov` and
+//! `polkadot_proxy`. Delete it once the keystore is created.
+use pdao_keystore::{file, Signer, Sr25519Signer};
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
+
+fn get_passphrase() -> anyhow::Result<Zeroizing<String>> {
+    let passphrase = std::env::var("PDAO_KEYSTORE_PASSPHRASE")
+        .map_err(|_| anyhow::anyhow!("PDAO_KEYSTORE_PASSPHRASE is not set."))?;
+    if passphrase.is_empty() {
+        anyhow::bail!("PDAO_KEYSTORE_PASSPHRASE is empty.");
+    }
+    Ok(Zeroizing::new(passphrase))
+}
+
+fn encrypt(secrets_file_path: &str, keystore_file_path: &str) -> anyhow::Result<()> {
+    let content = Zeroizing::new(std::fs::read_to_string(secrets_file_path)?);
+    let secrets: BTreeMap<String, String> = serde_json::from_str(&content)
+        .map_err(|_| anyhow::anyhow!("The secrets file must be a JSON object of strings."))?;
+    for (name, secret_uri) in secrets.iter() {
+        Sr25519Signer::from_secret_uri(secret_uri)
+            .map_err(|error| anyhow::anyhow!("Key {name}: {error}"))?;
+    }
+    std::fs::write(
+        keystore_file_path,
+        file::encrypt(&secrets, &get_passphrase()?)?,
+    )?;
+    println!(
+        "Keystore with {} keys written to {keystore_file_path}.",
+        secrets.len()
+    );
+    Ok(())
+}
+
+fn list(keystore_file_path: &str) -> anyhow::Result<()> {
+    let content = std::fs::read_to_string(keystore_file_path)?;
+    for (name, secret_uri) in file::decrypt(&content, &get_passphrase()?)? {
+        let signer = Sr25519Signer::from_secret_uri(&secret_uri)
+            .map_err(|error| anyhow::anyhow!("Key {name}: {error}"))?;
+        println!("{name}: {}", signer.account_id());
+    }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[1..] {
+        ["encrypt", secrets_file_path, keystore_file_path] => {
+            encrypt(secrets_file_path, keystore_file_path)
+        }
+        ["list", keystore_file_path] => list(keystore_file_path),
+        _ => anyhow::bail!(
+            "Usage:\n  pdao-keystore encrypt <secrets_json_file> <keystore_file>\n  pdao-keystore list <keystore_file>"
+        ),
+    }
+}
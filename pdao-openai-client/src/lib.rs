@@ -103,13 +103,16 @@ impl OpenAIClient {
         let json = serde_json::to_string(&sub_square_referendum)?;
         prompt_parts.push(serde_json::to_string(&json)?);
         let vote = match vote {
+            PolicyEvaluation::MinimumVoteCountNotMet { .. } => "NO VOTE",
             PolicyEvaluation::AbstainThresholdNotMet { .. } => "ABSTAIN",
             PolicyEvaluation::ParticipationNotMet { .. } => "NO VOTE",
+            PolicyEvaluation::CoreQuorumNotMet { .. } => "NO VOTE",
             PolicyEvaluation::AyeAbstainMajorityAbstain { .. } => "ABSTAIN",
             PolicyEvaluation::MajorityAbstain { .. } => "ABSTAIN",
             PolicyEvaluation::AyeEqualsNayAbstain { .. } => "ABSTAIN",
             PolicyEvaluation::Aye { .. } => "AYE",
             PolicyEvaluation::Nay { .. } => "NAY",
+            PolicyEvaluation::Split { .. } => "SPLIT, proportional to the member votes",
         };
         prompt_parts.push(format!(
             "The vote of the DAO on this referendum determined by its voting policy that applies to this referendum is {vote}.",
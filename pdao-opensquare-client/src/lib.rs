@@ -1,5 +1,6 @@
 use chrono::Utc;
 use pdao_config::Config;
+use pdao_keystore::{Keystore, GOV_KEY_NAME};
 use pdao_types::governance::opensquare::{
     OpenSquareAppendantRequest, OpenSquareAppendantRequestData, OpenSquareAppendantResponse,
     OpenSquareIPFSReferendumVote, OpenSquareNewProposal, OpenSquareNewProposalRequest,
@@ -10,8 +11,7 @@ use pdao_types::governance::opensquare::{
 use pdao_types::governance::subsquare::SubSquareReferendum;
 use pdao_types::governance::track::Track;
 use pdao_types::substrate::chain::Chain;
-use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
-use sp_core::{sr25519, Pair};
+use std::sync::Arc;
 
 fn ellipsize(input: &str, limit: usize) -> String {
     // If input is already within the limit, just return it
@@ -27,12 +27,14 @@ fn ellipsize(input: &str, limit: usize) -> String {
 pub struct OpenSquareClient {
     config: Config,
     http_client: reqwest::Client,
+    keystore: Arc<Keystore>,
 }
 
 impl OpenSquareClient {
-    pub fn new(config: &Config) -> anyhow::Result<Self> {
+    pub fn new(config: &Config, keystore: Arc<Keystore>) -> anyhow::Result<Self> {
         Ok(Self {
             config: config.clone(),
+            keystore,
             http_client: reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(
                     config.http.request_timeout_seconds,
@@ -93,11 +95,10 @@ impl OpenSquareClient {
             chain.token_ticker,
             referendum.referendum_index
         );
-        let pair = sr25519::Pair::from_string(&self.config.substrate.gov_proxy_seed_phrase, None)
-            .expect("Invalid seed phrase");
-        let address = pair
-            .public()
-            .to_ss58check_with_version(Ss58AddressFormat::from(chain.ss58_prefix));
+        let signer = self.keystore.get_signer(GOV_KEY_NAME)?;
+        let address = signer
+            .account_id()
+            .to_ss58_check_with_version(chain.ss58_prefix);
 
         let content = format!(
             "https://{}.subsquare.io/referenda/{}\n\n{}",
@@ -123,7 +124,7 @@ impl OpenSquareClient {
             content,
         );
         let proposal_json = serde_json::to_string(&proposal)?;
-        let signature = pair.sign(proposal_json.as_bytes());
+        let signature = signer.sign(proposal_json.as_bytes());
         let signature_hex = format!("0x{}", hex::encode(signature));
         let request = OpenSquareNewProposalRequest {
             data: proposal,
@@ -168,11 +169,10 @@ impl OpenSquareClient {
             "Terminate OpenSquare proposal for {} referendum with CID {cid}.",
             chain.token_ticker,
         );
-        let pair = sr25519::Pair::from_string(&self.config.substrate.gov_proxy_seed_phrase, None)
-            .expect("Invalid seed phrase");
-        let address = pair
-            .public()
-            .to_ss58check_with_version(Ss58AddressFormat::from(chain.ss58_prefix));
+        let signer = self.keystore.get_signer(GOV_KEY_NAME)?;
+        let address = signer
+            .account_id()
+            .to_ss58_check_with_version(chain.ss58_prefix);
         let request_data = OpenSquareTerminateProposalRequestData {
             action: "terminate".to_string(),
             proposal_cid: cid.to_string(),
@@ -181,7 +181,7 @@ impl OpenSquareClient {
             timestamp: (Utc::now().timestamp_millis() / 1000) as u64,
         };
         let proposal_json = serde_json::to_string(&request_data)?;
-        let signature = pair.sign(proposal_json.as_bytes());
+        let signature = signer.sign(proposal_json.as_bytes());
         let signature_hex = format!("0x{}", hex::encode(signature));
         let request = OpenSquareTerminateProposalRequest {
             data: request_data,
@@ -228,11 +228,10 @@ impl OpenSquareClient {
         content: &str,
     ) -> anyhow::Result<OpenSquareAppendantResponse> {
         log::info!("Make appendant to OpenSquare referendum with CID {cid}.");
-        let pair = sr25519::Pair::from_string(&self.config.substrate.gov_proxy_seed_phrase, None)
-            .expect("Invalid seed phrase");
-        let address = pair
-            .public()
-            .to_ss58check_with_version(Ss58AddressFormat::from(chain.ss58_prefix));
+        let signer = self.keystore.get_signer(GOV_KEY_NAME)?;
+        let address = signer
+            .account_id()
+            .to_ss58_check_with_version(chain.ss58_prefix);
         let request_data = OpenSquareAppendantRequestData {
             proposal_cid: cid.to_string(),
             content: content.to_string(),
@@ -242,7 +241,7 @@ impl OpenSquareClient {
             timestamp: (Utc::now().timestamp_millis() / 1000) as u64,
         };
         let request_json = serde_json::to_string(&request_data)?;
-        let signature = pair.sign(request_json.as_bytes());
+        let signature = signer.sign(request_json.as_bytes());
         let signature_hex = format!("0x{}", hex::encode(signature));
         let request = OpenSquareAppendantRequest {
             data: request_data,
@@ -0,0 +1,97 @@
+use crate::postgres::PostgreSQLStorage;
+use chrono::NaiveDateTime;
+use pdao_types::audit::AuditLogEntry;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+struct AuditLogRow {
+    pub id: i32,
+    pub actor: String,
+    pub actor_telegram_user_id: Option<i64>,
+    pub command: String,
+    pub arguments: String,
+    pub referendum_id: Option<i32>,
+    pub telegram_chat_id: Option<i64>,
+    pub telegram_thread_id: Option<i32>,
+    pub is_success: bool,
+    pub error: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+fn audit_log_row_into_audit_log_entry(row: AuditLogRow) -> anyhow::Result<AuditLogEntry> {
+    Ok(AuditLogEntry {
+        id: row.id as u32,
+        actor: row.actor,
+        actor_telegram_user_id: row.actor_telegram_user_id,
+        command: row.command,
+        arguments: serde_json::from_str(&row.arguments)?,
+        referendum_id: row.referendum_id.map(|id| id as u32),
+        telegram_chat_id: row.telegram_chat_id,
+        telegram_thread_id: row.telegram_thread_id,
+        is_success: row.is_success,
+        error: row.error,
+        created_at: row.created_at,
+    })
+}
+
+impl PostgreSQLStorage {
+    /// Records an action, successful when there's no error.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn save_audit_log_entry(
+        &self,
+        actor: &str,
+        actor_telegram_user_id: Option<i64>,
+        command: &str,
+        arguments: &[String],
+        referendum_id: Option<u32>,
+        telegram_chat_id: Option<i64>,
+        telegram_thread_id: Option<i32>,
+        error: Option<&str>,
+    ) -> anyhow::Result<i32> {
+        let result: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO pdao_audit_log (actor, actor_telegram_user_id, command, arguments, referendum_id, telegram_chat_id, telegram_thread_id, is_success, error)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+            .bind(actor)
+            .bind(actor_telegram_user_id)
+            .bind(command)
+            .bind(serde_json::to_string(arguments)?)
+            .bind(referendum_id.map(|id| id as i32))
+            .bind(telegram_chat_id)
+            .bind(telegram_thread_id)
+            .bind(error.is_none())
+            .bind(error)
+            .fetch_one(&self.connection_pool)
+            .await?;
+        Ok(result.0)
+    }
+
+    /// The latest entries of a topic, most recent first.
+    pub async fn get_thread_audit_log_entries(
+        &self,
+        telegram_chat_id: i64,
+        telegram_thread_id: i32,
+        limit: u32,
+    ) -> anyhow::Result<Vec<AuditLogEntry>> {
+        let rows: Vec<AuditLogRow> = sqlx::query_as(
+            r#"
+            SELECT id, actor, actor_telegram_user_id, command, arguments, referendum_id, telegram_chat_id, telegram_thread_id, is_success, error, created_at
+            FROM pdao_audit_log
+            WHERE telegram_chat_id = $1 AND telegram_thread_id = $2
+            ORDER BY id DESC
+            LIMIT $3
+            "#,
+        )
+            .bind(telegram_chat_id)
+            .bind(telegram_thread_id)
+            .bind(limit as i64)
+            .fetch_all(&self.connection_pool)
+            .await?;
+        rows.into_iter()
+            .map(audit_log_row_into_audit_log_entry)
+            .collect()
+    }
+}
@@ -0,0 +1,46 @@
+use crate::postgres::PostgreSQLStorage;
+use sqlx::{Connection, PgConnection};
+
+/// Namespace of the referendum advisory locks, the referendum id being the second key.
+const REFERENDUM_LOCK_CLASS: i32 = 1;
+
+/// Session advisory lock of a referendum, held for as long as the guard lives. It's held on a
+/// connection of its own outside the pool, since a vote can wait for an offline signature for
+/// minutes, and dropping the guard closes the connection, which releases the lock.
+pub struct ReferendumLock {
+    _connection: PgConnection,
+}
+
+impl PostgreSQLStorage {
+    /// Takes the lock of the referendum, `None` when another action holds it.
+    pub async fn try_lock_referendum(
+        &self,
+        referendum_id: u32,
+    ) -> anyhow::Result<Option<ReferendumLock>> {
+        self.try_lock_referenda(&[referendum_id]).await
+    }
+
+    /// Takes the locks of all the referenda on a single connection, `None` when another action
+    /// holds any of them.
+    pub async fn try_lock_referenda(
+        &self,
+        referendum_ids: &[u32],
+    ) -> anyhow::Result<Option<ReferendumLock>> {
+        // dropped with the locks already taken when a later one is held elsewhere
+        let mut connection =
+            PgConnection::connect_with(&self.connection_pool.connect_options()).await?;
+        for referendum_id in referendum_ids.iter() {
+            let (is_locked,): (bool,) = sqlx::query_as("SELECT pg_try_advisory_lock($1, $2)")
+                .bind(REFERENDUM_LOCK_CLASS)
+                .bind(*referendum_id as i32)
+                .fetch_one(&mut connection)
+                .await?;
+            if !is_locked {
+                return Ok(None);
+            }
+        }
+        Ok(Some(ReferendumLock {
+            _connection: connection,
+        }))
+    }
+}
@@ -2,10 +2,15 @@ use pdao_config::Config;
 use sqlx::{Pool, Postgres, Transaction};
 use std::time::Duration;
 
+pub mod audit;
+pub mod lock;
 pub mod member;
+pub mod outbox;
 pub mod referendum;
+pub mod role;
 pub mod settings;
 pub mod vote;
+pub mod vote_intent;
 
 pub struct PostgreSQLStorage {
     connection_pool: Pool<Postgres>,
@@ -0,0 +1,111 @@
+use crate::postgres::PostgreSQLStorage;
+use pdao_types::outbox::{OutboxEffect, OutboxItem, OutboxItemStatus};
+
+type OutboxItemRecord = (i32, String, String, i32);
+
+impl PostgreSQLStorage {
+    /// Enqueues the effect, unless an item with the same idempotency key is already enqueued.
+    pub async fn enqueue_outbox_item(
+        &self,
+        idempotency_key: &str,
+        effect: &OutboxEffect,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pdao_outbox (idempotency_key, payload, status)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(idempotency_key) DO NOTHING
+            "#,
+        )
+        .bind(idempotency_key)
+        .bind(serde_json::to_string(effect)?)
+        .bind(OutboxItemStatus::Pending)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Claims the next due item by counting the attempt and postponing it for the lease, so
+    /// that concurrent workers skip it and a crashed delivery gets retried after the lease.
+    pub async fn claim_outbox_item(
+        &self,
+        lease_seconds: u64,
+    ) -> anyhow::Result<Option<OutboxItem>> {
+        let maybe_record: Option<OutboxItemRecord> = sqlx::query_as(
+            r#"
+            UPDATE pdao_outbox
+            SET attempt_count = attempt_count + 1, next_attempt_at = now() + make_interval(secs => $1)
+            WHERE id = (
+                SELECT id FROM pdao_outbox
+                WHERE status = $2 AND next_attempt_at <= now()
+                ORDER BY id ASC
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, idempotency_key, payload, attempt_count
+            "#,
+        )
+            .bind(lease_seconds as f64)
+            .bind(OutboxItemStatus::Pending)
+            .fetch_optional(&self.connection_pool)
+            .await?;
+        let Some(record) = maybe_record else {
+            return Ok(None);
+        };
+        Ok(Some(OutboxItem {
+            id: record.0 as u32,
+            idempotency_key: record.1,
+            effect: serde_json::from_str(&record.2)?,
+            attempt_count: record.3 as u32,
+        }))
+    }
+
+    pub async fn set_outbox_item_delivered(&self, id: u32) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE pdao_outbox SET status = $1, delivered_at = now(), last_error = NULL
+            WHERE id = $2
+            "#,
+        )
+        .bind(OutboxItemStatus::Delivered)
+        .bind(id as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_outbox_item_retry(
+        &self,
+        id: u32,
+        delay_seconds: u64,
+        error: &str,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE pdao_outbox SET next_attempt_at = now() + make_interval(secs => $1), last_error = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(delay_seconds as f64)
+        .bind(error)
+        .bind(id as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_outbox_item_failed(&self, id: u32, error: &str) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE pdao_outbox SET status = $1, last_error = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(OutboxItemStatus::Failed)
+        .bind(error)
+        .bind(id as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use crate::postgres::PostgreSQLStorage;
 use pdao_types::governance::subsquare::SubSquareReferendum as OpensquareReferendum;
 use pdao_types::governance::track::Track;
 use pdao_types::governance::{Referendum, ReferendumStatus};
+use pdao_types::substrate::referendum::ReferendumLookup;
 use sqlx::FromRow;
 
 #[derive(Debug, FromRow)]
@@ -24,9 +25,23 @@ struct ReferendumRow {
     pub has_coi: bool,
     pub is_archived: bool,
     pub preimage_exists: bool,
+    pub preimage_hash: Option<String>,
+    pub preimage_length: Option<i32>,
 }
 
 fn referendum_row_into_referendum(row: &ReferendumRow) -> anyhow::Result<Referendum> {
+    let preimage_lookup = match (&row.preimage_hash, row.preimage_length) {
+        (Some(hash), Some(length)) => {
+            let hash: [u8; 32] = hex::decode(hash.trim_start_matches("0x"))?
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Invalid preimage hash: {hash}"))?;
+            Some(ReferendumLookup {
+                hash,
+                length: length as u32,
+            })
+        }
+        _ => None,
+    };
     Ok(Referendum {
         id: row.id as u32,
         network_id: row.network_id as u32,
@@ -46,6 +61,7 @@ fn referendum_row_into_referendum(row: &ReferendumRow) -> anyhow::Result<Referen
         has_coi: row.has_coi,
         is_archived: row.is_archived,
         preimage_exists: row.preimage_exists,
+        preimage_lookup,
     })
 }
 
@@ -55,6 +71,7 @@ impl PostgreSQLStorage {
         network_id: u32,
         referendum: &OpensquareReferendum,
         preimage_exists: bool,
+        maybe_preimage_lookup: Option<&ReferendumLookup>,
         opensquare_cid: &str,
         opensquare_post_uid: &str,
         telegram_chat_id: i64,
@@ -62,10 +79,10 @@ impl PostgreSQLStorage {
     ) -> anyhow::Result<i32> {
         let result: (i32,) = sqlx::query_as(
             r#"
-            INSERT INTO pdao_referendum (network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, preimage_exists)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            INSERT INTO pdao_referendum (network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, preimage_exists, preimage_hash, preimage_length)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             ON CONFLICT(network_id, index) DO UPDATE
-            SET track_id = EXCLUDED.track_id, title = EXCLUDED.title, content = EXCLUDED.content, content_type = EXCLUDED.content_type, telegram_chat_id = EXCLUDED.telegram_chat_id, telegram_topic_id = EXCLUDED.telegram_topic_id, telegram_intro_message_id = EXCLUDED.telegram_intro_message_id, opensquare_cid = EXCLUDED.opensquare_cid, opensquare_post_uid = EXCLUDED.opensquare_post_uid, preimage_exists = EXCLUDED.preimage_exists
+            SET track_id = EXCLUDED.track_id, title = EXCLUDED.title, content = EXCLUDED.content, content_type = EXCLUDED.content_type, telegram_chat_id = EXCLUDED.telegram_chat_id, telegram_topic_id = EXCLUDED.telegram_topic_id, telegram_intro_message_id = EXCLUDED.telegram_intro_message_id, opensquare_cid = EXCLUDED.opensquare_cid, opensquare_post_uid = EXCLUDED.opensquare_post_uid, preimage_exists = EXCLUDED.preimage_exists, preimage_hash = EXCLUDED.preimage_hash, preimage_length = EXCLUDED.preimage_length
             RETURNING id
             "#,
         )
@@ -82,6 +99,8 @@ impl PostgreSQLStorage {
             .bind(opensquare_cid)
             .bind(opensquare_post_uid)
             .bind(preimage_exists)
+            .bind(maybe_preimage_lookup.map(|lookup| format!("0x{}", hex::encode(lookup.hash))))
+            .bind(maybe_preimage_lookup.map(|lookup| lookup.length as i32))
             .fetch_one(&self.connection_pool)
             .await?;
         Ok(result.0)
@@ -90,7 +109,7 @@ impl PostgreSQLStorage {
     pub async fn get_referendum_by_id(&self, id: u32) -> anyhow::Result<Option<Referendum>> {
         let maybe_row: Option<ReferendumRow> = sqlx::query_as::<_, ReferendumRow>(
             r#"
-            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists
+            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists, preimage_hash, preimage_length
             FROM pdao_referendum
             WHERE id = $1
             "#,
@@ -112,7 +131,7 @@ impl PostgreSQLStorage {
     ) -> anyhow::Result<Option<Referendum>> {
         let maybe_row: Option<ReferendumRow> = sqlx::query_as::<_, ReferendumRow>(
             r#"
-            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists
+            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists, preimage_hash, preimage_length
             FROM pdao_referendum
             WHERE network_id = $1 AND index = $2
             "#,
@@ -131,7 +150,7 @@ impl PostgreSQLStorage {
     pub async fn get_all_referenda(&self, network_id: u32) -> anyhow::Result<Vec<Referendum>> {
         let rows: Vec<ReferendumRow> = sqlx::query_as::<_, ReferendumRow>(
             r#"
-            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists
+            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists, preimage_hash, preimage_length
             FROM pdao_referendum
             WHERE network_id = $1
             "#,
@@ -153,7 +172,7 @@ impl PostgreSQLStorage {
     ) -> anyhow::Result<Vec<Referendum>> {
         let rows: Vec<ReferendumRow> = sqlx::query_as::<_, ReferendumRow>(
             r#"
-            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists
+            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists, preimage_hash, preimage_length
             FROM pdao_referendum
             WHERE network_id = $1 AND status = ANY ($2::text[])
             "#,
@@ -176,7 +195,7 @@ impl PostgreSQLStorage {
     ) -> anyhow::Result<Option<Referendum>> {
         let maybe_row: Option<ReferendumRow> = sqlx::query_as::<_, ReferendumRow>(
             r#"
-            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists
+            SELECT id, network_id, track_id, index, status, title, content, content_type, telegram_chat_id, telegram_topic_id, telegram_intro_message_id, opensquare_cid, opensquare_post_uid, last_vote_id, is_terminated, has_coi, is_archived, preimage_exists, preimage_hash, preimage_length
             FROM pdao_referendum
             WHERE telegram_chat_id = $1 AND telegram_topic_id = $2
             "#,
@@ -300,4 +319,24 @@ impl PostgreSQLStorage {
         .await?;
         Ok(maybe_result.map(|r| r.0))
     }
+
+    pub async fn set_referendum_preimage_lookup(
+        &self,
+        referendum_id: u32,
+        maybe_preimage_lookup: Option<&ReferendumLookup>,
+    ) -> anyhow::Result<Option<i32>> {
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE pdao_referendum SET preimage_hash = $1, preimage_length = $2
+            WHERE id = $3
+            RETURNING id
+            "#,
+        )
+        .bind(maybe_preimage_lookup.map(|lookup| format!("0x{}", hex::encode(lookup.hash))))
+        .bind(maybe_preimage_lookup.map(|lookup| lookup.length as i32))
+        .bind(referendum_id as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_result.map(|r| r.0))
+    }
 }
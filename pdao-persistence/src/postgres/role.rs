@@ -0,0 +1,110 @@
+use crate::postgres::PostgreSQLStorage;
+use pdao_types::Role;
+use sqlx::{Postgres, Transaction};
+
+impl PostgreSQLStorage {
+    pub async fn get_user_roles(&self, telegram_user_id: i64) -> anyhow::Result<Vec<Role>> {
+        let records: Vec<(Role,)> = sqlx::query_as(
+            r#"
+            SELECT role FROM pdao_user_role
+            WHERE telegram_user_id = $1
+            "#,
+        )
+        .bind(telegram_user_id)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(records.into_iter().map(|record| record.0).collect())
+    }
+
+    async fn save_user_role_change(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        telegram_user_id: i64,
+        role: Role,
+        is_granted: bool,
+        actor_telegram_user_id: i64,
+        actor_username: &str,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pdao_user_role_change (telegram_user_id, role, is_granted, actor_telegram_user_id, actor_username)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+            .bind(telegram_user_id)
+            .bind(role)
+            .bind(is_granted)
+            .bind(actor_telegram_user_id)
+            .bind(actor_username)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Grants the role and records the change, `false` when the user already has it.
+    pub async fn grant_user_role(
+        &self,
+        telegram_user_id: i64,
+        role: Role,
+        actor_telegram_user_id: i64,
+        actor_username: &str,
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.begin_tx().await?;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO pdao_user_role (telegram_user_id, role)
+            VALUES ($1, $2)
+            ON CONFLICT(telegram_user_id, role) DO NOTHING
+            "#,
+        )
+        .bind(telegram_user_id)
+        .bind(role)
+        .execute(&mut *tx)
+        .await?;
+        let is_granted = result.rows_affected() == 1;
+        if is_granted {
+            self.save_user_role_change(
+                &mut tx,
+                telegram_user_id,
+                role,
+                true,
+                actor_telegram_user_id,
+                actor_username,
+            )
+            .await?;
+        }
+        self.commit_tx(tx).await?;
+        Ok(is_granted)
+    }
+
+    /// Revokes the role and records the change, `false` when the user doesn't have it.
+    pub async fn revoke_user_role(
+        &self,
+        telegram_user_id: i64,
+        role: Role,
+        actor_telegram_user_id: i64,
+        actor_username: &str,
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.begin_tx().await?;
+        let result =
+            sqlx::query("DELETE FROM pdao_user_role WHERE telegram_user_id = $1 AND role = $2")
+                .bind(telegram_user_id)
+                .bind(role)
+                .execute(&mut *tx)
+                .await?;
+        let is_revoked = result.rows_affected() == 1;
+        if is_revoked {
+            self.save_user_role_change(
+                &mut tx,
+                telegram_user_id,
+                role,
+                false,
+                actor_telegram_user_id,
+                actor_username,
+            )
+            .await?;
+        }
+        self.commit_tx(tx).await?;
+        Ok(is_revoked)
+    }
+}
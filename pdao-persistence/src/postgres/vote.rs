@@ -1,25 +1,37 @@
 use crate::postgres::PostgreSQLStorage;
-use pdao_types::governance::{MemberVote, PendingMemberVote, Vote};
+use pdao_types::governance::{
+    AccountVote, MemberVote, PendingMemberVote, Vote, VotePolicyRecord, VoteSplit,
+};
 use pdao_types::substrate::account_id::AccountId;
+use sqlx::{FromRow, Postgres, Transaction};
 use std::str::FromStr;
 
-type VoteRecord = (
-    i32,
-    i32,
-    i32,
-    i32,
-    String,
-    i64,
-    i32,
-    Option<bool>,
-    String,
-    i32,
-    bool,
-    Option<String>,
-    Option<i32>,
-    bool,
-    bool,
-);
+#[derive(Debug, FromRow)]
+struct VoteRow {
+    pub id: i32,
+    pub network_id: i32,
+    pub referendum_id: i32,
+    pub index: i32,
+    pub block_hash: String,
+    pub block_number: i64,
+    pub extrinsic_index: i32,
+    pub vote: Option<bool>,
+    pub balance: String,
+    pub conviction: i32,
+    pub is_removed: bool,
+    pub subsquare_comment_cid: Option<String>,
+    pub subsquare_comment_index: Option<i32>,
+    pub has_coi: bool,
+    pub is_forced: bool,
+    pub policy_version: Option<String>,
+    pub policy: Option<String>,
+    pub vote_counts: Option<String>,
+    pub evaluation_description: Option<String>,
+    pub blocks_left: Option<i64>,
+    pub vote_split: Option<String>,
+    pub account_votes: Option<String>,
+    pub is_cleaned_up: bool,
+}
 
 type MemberVoteRecord = (
     i32,
@@ -35,23 +47,56 @@ type MemberVoteRecord = (
 
 type PendingMemberVoteRecord = (i32, String, i32, i32, i32, String, Option<bool>, String);
 
-fn vote_record_into_vote(record: &VoteRecord) -> anyhow::Result<Vote> {
+fn vote_row_into_vote(row: &VoteRow) -> anyhow::Result<Vote> {
+    let policy_record = match (
+        &row.policy_version,
+        &row.policy,
+        &row.vote_counts,
+        &row.evaluation_description,
+    ) {
+        (Some(policy_version), Some(policy), Some(vote_counts), Some(evaluation_description)) => {
+            Some(VotePolicyRecord {
+                policy_version: policy_version.clone(),
+                policy: serde_json::from_str(policy)?,
+                vote_counts: serde_json::from_str(vote_counts)?,
+                blocks_left: row.blocks_left.map(|blocks_left| blocks_left as u64),
+                description_lines: evaluation_description
+                    .split('\n')
+                    .map(String::from)
+                    .collect(),
+            })
+        }
+        _ => None,
+    };
     Ok(Vote {
-        id: record.0 as u32,
-        network_id: record.1 as u32,
-        referendum_id: record.2 as u32,
-        index: record.3 as u32,
-        block_hash: record.4.clone(),
-        block_number: record.5 as u64,
-        extrinsic_index: record.6 as u32,
-        vote: record.7,
-        balance: record.8.parse()?,
-        conviction: record.9 as u32,
-        is_removed: record.10,
-        subsquare_comment_cid: record.11.clone(),
-        subsquare_comment_index: record.12.map(|i| i as u32),
-        has_coi: record.13,
-        is_forced: record.14,
+        id: row.id as u32,
+        network_id: row.network_id as u32,
+        referendum_id: row.referendum_id as u32,
+        index: row.index as u32,
+        block_hash: row.block_hash.clone(),
+        block_number: row.block_number as u64,
+        extrinsic_index: row.extrinsic_index as u32,
+        vote: row.vote,
+        balance: row.balance.parse()?,
+        conviction: row.conviction as u32,
+        is_removed: row.is_removed,
+        subsquare_comment_cid: row.subsquare_comment_cid.clone(),
+        subsquare_comment_index: row.subsquare_comment_index.map(|i| i as u32),
+        has_coi: row.has_coi,
+        is_forced: row.is_forced,
+        policy_record,
+        split: row
+            .vote_split
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()?,
+        account_votes: row
+            .account_votes
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()?
+            .unwrap_or_default(),
+        is_cleaned_up: row.is_cleaned_up,
     })
 }
 
@@ -88,6 +133,7 @@ impl PostgreSQLStorage {
     #[allow(clippy::too_many_arguments)]
     pub async fn save_vote(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         network_id: u32,
         referendum_id: u32,
         referendum_index: u32,
@@ -95,17 +141,28 @@ impl PostgreSQLStorage {
         block_number: u64,
         extrinsic_index: u32,
         vote: Option<bool>,
+        split: Option<VoteSplit>,
         balance: u128,
         conviction: u8,
         subsquare_comment_cid: Option<&str>,
         subsquare_comment_index: Option<u32>,
         has_coi: bool,
         is_forced: bool,
+        policy_record: Option<&VotePolicyRecord>,
+        account_votes: &[AccountVote],
     ) -> anyhow::Result<i32> {
+        let (policy, vote_counts) = if let Some(policy_record) = policy_record {
+            (
+                Some(serde_json::to_string(&policy_record.policy)?),
+                Some(serde_json::to_string(&policy_record.vote_counts)?),
+            )
+        } else {
+            (None, None)
+        };
         let result: (i32,) = sqlx::query_as(
             r#"
-            INSERT INTO pdao_vote (network_id, referendum_id, index, block_hash, block_number, extrinsic_index, vote, balance, conviction, subsquare_comment_cid, subsquare_comment_index, has_coi, is_forced)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            INSERT INTO pdao_vote (network_id, referendum_id, index, block_hash, block_number, extrinsic_index, vote, balance, conviction, subsquare_comment_cid, subsquare_comment_index, has_coi, is_forced, policy_version, policy, vote_counts, evaluation_description, blocks_left, vote_split, account_votes)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
             RETURNING id
             "#,
         )
@@ -122,12 +179,27 @@ impl PostgreSQLStorage {
             .bind(subsquare_comment_index.map(|index| index as i32))
             .bind(has_coi)
             .bind(is_forced)
-            .fetch_one(&self.connection_pool)
+            .bind(policy_record.map(|record| record.policy_version.as_str()))
+            .bind(policy)
+            .bind(vote_counts)
+            .bind(policy_record.map(|record| record.description_lines.join("\n")))
+            .bind(
+                policy_record
+                    .and_then(|record| record.blocks_left)
+                    .map(|blocks_left| blocks_left as i64),
+            )
+            .bind(split.map(|split| serde_json::to_string(&split)).transpose()?)
+            .bind(serde_json::to_string(account_votes)?)
+            .fetch_one(&mut **tx)
             .await?;
         Ok(result.0)
     }
 
-    pub async fn set_vote_removed(&self, vote_id: u32) -> anyhow::Result<Option<i32>> {
+    pub async fn set_vote_removed(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        vote_id: u32,
+    ) -> anyhow::Result<Option<i32>> {
         let maybe_result: Option<(i32,)> = sqlx::query_as(
             r#"
             UPDATE pdao_vote SET is_removed = true
@@ -136,18 +208,59 @@ impl PostgreSQLStorage {
             "#,
         )
         .bind(vote_id as i32)
+        .fetch_optional(&mut **tx)
+        .await?;
+        Ok(maybe_result.map(|r| r.0))
+    }
+
+    pub async fn set_vote_subsquare_comment(
+        &self,
+        vote_id: u32,
+        subsquare_comment_cid: &str,
+        subsquare_comment_index: u32,
+    ) -> anyhow::Result<Option<i32>> {
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE pdao_vote SET subsquare_comment_cid = $1, subsquare_comment_index = $2
+            WHERE id = $3
+            RETURNING id
+            "#,
+        )
+        .bind(subsquare_comment_cid)
+        .bind(subsquare_comment_index as i32)
+        .bind(vote_id as i32)
         .fetch_optional(&self.connection_pool)
         .await?;
         Ok(maybe_result.map(|r| r.0))
     }
 
+    /// Marks the votes on the referendum as cleaned up, once the voting accounts' votes on it
+    /// have been removed on chain and their locks released.
+    pub async fn set_referendum_votes_cleaned_up(
+        &self,
+        network_id: u32,
+        referendum_index: u32,
+    ) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE pdao_vote SET is_cleaned_up = true
+            WHERE network_id = $1 AND index = $2
+            "#,
+        )
+        .bind(network_id as i32)
+        .bind(referendum_index as i32)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn get_referendum_last_vote(
         &self,
         referendum_id: u32,
     ) -> anyhow::Result<Option<Vote>> {
-        let db_vote: Option<VoteRecord> = sqlx::query_as(
+        let maybe_row: Option<VoteRow> = sqlx::query_as::<_, VoteRow>(
             r#"
-            SELECT id, network_id, referendum_id, index, block_hash, block_number, extrinsic_index, vote, balance, conviction, is_removed, subsquare_comment_cid, subsquare_comment_index, has_coi, is_forced
+            SELECT id, network_id, referendum_id, index, block_hash, block_number, extrinsic_index, vote, balance, conviction, is_removed, subsquare_comment_cid, subsquare_comment_index, has_coi, is_forced, policy_version, policy, vote_counts, evaluation_description, blocks_left, vote_split, account_votes, is_cleaned_up
             FROM pdao_vote
             WHERE referendum_id = $1
             ORDER BY id DESC
@@ -157,17 +270,17 @@ impl PostgreSQLStorage {
             .bind(referendum_id as i32)
             .fetch_optional(&self.connection_pool)
             .await?;
-        if let Some(db_vote) = db_vote {
-            Ok(Some(vote_record_into_vote(&db_vote)?))
+        if let Some(row) = maybe_row {
+            Ok(Some(vote_row_into_vote(&row)?))
         } else {
             Ok(None)
         }
     }
 
     pub async fn get_referendum_votes(&self, referendum_id: u32) -> anyhow::Result<Vec<Vote>> {
-        let db_votes: Vec<VoteRecord> = sqlx::query_as(
+        let rows: Vec<VoteRow> = sqlx::query_as::<_, VoteRow>(
             r#"
-            SELECT id, network_id, referendum_id, index, block_hash, block_number, extrinsic_index, vote, balance, conviction, is_removed, subsquare_comment_cid, subsquare_comment_index, has_coi, is_forced
+            SELECT id, network_id, referendum_id, index, block_hash, block_number, extrinsic_index, vote, balance, conviction, is_removed, subsquare_comment_cid, subsquare_comment_index, has_coi, is_forced, policy_version, policy, vote_counts, evaluation_description, blocks_left, vote_split, account_votes, is_cleaned_up
             FROM pdao_vote
             WHERE referendum_id = $1
             ORDER BY id ASC
@@ -177,8 +290,8 @@ impl PostgreSQLStorage {
             .fetch_all(&self.connection_pool)
             .await?;
         let mut votes = Vec::new();
-        for db_vote in db_votes.iter() {
-            votes.push(vote_record_into_vote(db_vote)?);
+        for row in rows.iter() {
+            votes.push(vote_row_into_vote(row)?);
         }
         Ok(votes)
     }
@@ -198,6 +311,7 @@ impl PostgreSQLStorage {
 
     pub async fn set_referendum_last_vote_id(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         referendum_id: u32,
         vote_id: Option<u32>,
     ) -> anyhow::Result<Option<i32>> {
@@ -210,7 +324,7 @@ impl PostgreSQLStorage {
         )
         .bind(vote_id.map(|id| id as i32))
         .bind(referendum_id as i32)
-        .fetch_optional(&self.connection_pool)
+        .fetch_optional(&mut **tx)
         .await?;
         Ok(maybe_result.map(|r| r.0))
     }
@@ -218,6 +332,7 @@ impl PostgreSQLStorage {
     #[allow(clippy::too_many_arguments)]
     pub async fn save_member_vote(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         vote_id: u32,
         cid: &str,
         network_id: u32,
@@ -243,7 +358,7 @@ impl PostgreSQLStorage {
             .bind(address)
             .bind(vote)
             .bind(feedback)
-            .execute(&self.connection_pool)
+            .execute(&mut **tx)
             .await?;
         Ok(())
     }
@@ -350,11 +465,12 @@ impl PostgreSQLStorage {
 
     pub async fn delete_referendum_pending_member_votes(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         referendum_id: u32,
     ) -> anyhow::Result<()> {
         sqlx::query("DELETE FROM pdao_pending_member_vote WHERE referendum_id = $1")
             .bind(referendum_id as i32)
-            .execute(&self.connection_pool)
+            .execute(&mut **tx)
             .await?;
         Ok(())
     }
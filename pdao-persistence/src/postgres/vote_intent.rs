@@ -0,0 +1,222 @@
+use crate::postgres::PostgreSQLStorage;
+use pdao_types::governance::{VoteIntent, VoteIntentRecord, VoteIntentStatus};
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+struct VoteIntentRow {
+    pub id: i32,
+    pub network_id: i32,
+    pub referendum_id: i32,
+    pub index: i32,
+    pub status: VoteIntentStatus,
+    pub record: String,
+    pub block_hash: Option<String>,
+    pub block_number: Option<i64>,
+    pub extrinsic_index: Option<i32>,
+    pub vote_id: Option<i32>,
+}
+
+fn vote_intent_row_into_vote_intent(row: &VoteIntentRow) -> anyhow::Result<VoteIntent> {
+    Ok(VoteIntent {
+        id: row.id as u32,
+        network_id: row.network_id as u32,
+        referendum_id: row.referendum_id as u32,
+        index: row.index as u32,
+        status: row.status,
+        record: serde_json::from_str(&row.record)?,
+        block_hash: row.block_hash.clone(),
+        block_number: row.block_number.map(|block_number| block_number as u64),
+        extrinsic_index: row.extrinsic_index.map(|index| index as u32),
+        vote_id: row.vote_id.map(|id| id as u32),
+    })
+}
+
+impl PostgreSQLStorage {
+    pub async fn save_vote_intent(
+        &self,
+        network_id: u32,
+        referendum_id: u32,
+        referendum_index: u32,
+        record: &VoteIntentRecord,
+    ) -> anyhow::Result<i32> {
+        let result: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO pdao_vote_intent (network_id, referendum_id, index, status, record)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(network_id as i32)
+        .bind(referendum_id as i32)
+        .bind(referendum_index as i32)
+        .bind(VoteIntentStatus::Pending)
+        .bind(serde_json::to_string(record)?)
+        .fetch_one(&self.connection_pool)
+        .await?;
+        Ok(result.0)
+    }
+
+    pub async fn set_vote_intent_submitted(
+        &self,
+        id: u32,
+        block_hash: &str,
+        block_number: u64,
+        extrinsic_index: u32,
+    ) -> anyhow::Result<Option<i32>> {
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE pdao_vote_intent
+            SET status = $1, block_hash = $2, block_number = $3, extrinsic_index = $4, updated_at = now()
+            WHERE id = $5
+            RETURNING id
+            "#,
+        )
+        .bind(VoteIntentStatus::Submitted)
+        .bind(block_hash)
+        .bind(block_number as i64)
+        .bind(extrinsic_index as i32)
+        .bind(id as i32)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_result.map(|r| r.0))
+    }
+
+    /// Closes a pending intent that didn't make it to the chain, or whose outcome is unknown.
+    pub async fn set_vote_intent_unsubmitted(
+        &self,
+        id: u32,
+        status: VoteIntentStatus,
+        error: &str,
+    ) -> anyhow::Result<Option<i32>> {
+        let maybe_result: Option<(i32,)> = sqlx::query_as(
+            r#"
+            UPDATE pdao_vote_intent
+            SET status = $1, error = $2, updated_at = now()
+            WHERE id = $3 AND status = $4
+            RETURNING id
+            "#,
+        )
+        .bind(status)
+        .bind(error)
+        .bind(id as i32)
+        .bind(VoteIntentStatus::Pending)
+        .fetch_optional(&self.connection_pool)
+        .await?;
+        Ok(maybe_result.map(|r| r.0))
+    }
+
+    /// Intents that are pending or submitted, left behind by a crash or a failed recording.
+    pub async fn get_unrecorded_vote_intents(&self) -> anyhow::Result<Vec<VoteIntent>> {
+        let rows: Vec<VoteIntentRow> = sqlx::query_as(
+            r#"
+            SELECT id, network_id, referendum_id, index, status, record, block_hash, block_number, extrinsic_index, vote_id
+            FROM pdao_vote_intent
+            WHERE status = $1 OR status = $2
+            ORDER BY id ASC
+            "#,
+        )
+            .bind(VoteIntentStatus::Pending)
+            .bind(VoteIntentStatus::Submitted)
+            .fetch_all(&self.connection_pool)
+            .await?;
+        let mut vote_intents = Vec::new();
+        for row in rows.iter() {
+            vote_intents.push(vote_intent_row_into_vote_intent(row)?);
+        }
+        Ok(vote_intents)
+    }
+
+    /// Records the vote of a submitted intent, with its member votes, in a single transaction.
+    pub async fn record_vote(
+        &self,
+        vote_intent_id: u32,
+        subsquare_comment_cid: Option<&str>,
+        subsquare_comment_index: Option<u32>,
+    ) -> anyhow::Result<i32> {
+        let mut tx = self.begin_tx().await?;
+        // lock the intent so that a concurrent recovery doesn't record it twice
+        let maybe_row: Option<VoteIntentRow> = sqlx::query_as(
+            r#"
+            SELECT id, network_id, referendum_id, index, status, record, block_hash, block_number, extrinsic_index, vote_id
+            FROM pdao_vote_intent
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+            .bind(vote_intent_id as i32)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some(row) = maybe_row else {
+            anyhow::bail!("Vote intent {vote_intent_id} not found.");
+        };
+        let intent = vote_intent_row_into_vote_intent(&row)?;
+        if intent.status == VoteIntentStatus::Recorded {
+            if let Some(vote_id) = intent.vote_id {
+                return Ok(vote_id as i32);
+            }
+        }
+        let (Some(block_hash), Some(block_number), Some(extrinsic_index)) = (
+            intent.block_hash.as_deref(),
+            intent.block_number,
+            intent.extrinsic_index,
+        ) else {
+            anyhow::bail!("Vote intent {vote_intent_id} has not been submitted.");
+        };
+        let record = &intent.record;
+        let vote_id = self
+            .save_vote(
+                &mut tx,
+                intent.network_id,
+                intent.referendum_id,
+                intent.index,
+                block_hash,
+                block_number,
+                extrinsic_index,
+                record.vote,
+                record.split,
+                record.balance,
+                record.conviction,
+                subsquare_comment_cid,
+                subsquare_comment_index,
+                record.has_coi,
+                record.is_forced,
+                record.policy_record.as_ref(),
+                &record.account_votes,
+            )
+            .await?;
+        for member_vote in record.member_votes.iter() {
+            self.save_member_vote(
+                &mut tx,
+                vote_id as u32,
+                &member_vote.cid,
+                intent.network_id,
+                intent.referendum_id,
+                intent.index,
+                &member_vote.address,
+                member_vote.vote,
+                &member_vote.feedback,
+            )
+            .await?;
+        }
+        if !record.is_forced {
+            self.delete_referendum_pending_member_votes(&mut tx, intent.referendum_id)
+                .await?;
+        }
+        self.set_referendum_last_vote_id(&mut tx, intent.referendum_id, Some(vote_id as u32))
+            .await?;
+        sqlx::query(
+            r#"
+            UPDATE pdao_vote_intent
+            SET status = $1, vote_id = $2, updated_at = now()
+            WHERE id = $3
+            "#,
+        )
+        .bind(VoteIntentStatus::Recorded)
+        .bind(vote_id)
+        .bind(vote_intent_id as i32)
+        .execute(&mut *tx)
+        .await?;
+        self.commit_tx(tx).await?;
+        Ok(vote_id)
+    }
+}
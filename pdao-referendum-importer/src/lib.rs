@@ -1,11 +1,14 @@
 use pdao_config::Config;
 
+use pdao_keystore::Keystore;
 use pdao_opensquare_client::OpenSquareClient;
 use pdao_persistence::postgres::PostgreSQLStorage;
 use pdao_subsquare_client::SubSquareClient;
 use pdao_telegram_client::TelegramClient;
 use pdao_types::governance::Referendum;
 use pdao_types::substrate::chain::Chain;
+use pdao_types::substrate::referendum::{ProposalCall, ReferendumLookup};
+use std::sync::Arc;
 
 #[derive(thiserror::Error, Clone, Debug)]
 pub enum ReferendumImportError {
@@ -31,13 +34,13 @@ fn system_err(error: anyhow::Error, description: &str) -> ReferendumImportError
 }
 
 impl ReferendumImporter {
-    pub async fn new(config: &Config) -> anyhow::Result<Self> {
+    pub async fn new(config: &Config, keystore: Arc<Keystore>) -> anyhow::Result<Self> {
         Ok(Self {
             config: config.clone(),
             postgres: PostgreSQLStorage::new(config).await?,
             telegram_client: TelegramClient::new(config),
-            opensquare_client: OpenSquareClient::new(config)?,
-            subsquare_client: SubSquareClient::new(config)?,
+            opensquare_client: OpenSquareClient::new(config, keystore.clone())?,
+            subsquare_client: SubSquareClient::new(config, keystore)?,
         })
     }
 
@@ -47,6 +50,8 @@ impl ReferendumImporter {
         index: u32,
         snapshot_height: u64,
         preimage_exists: bool,
+        maybe_preimage_lookup: Option<&ReferendumLookup>,
+        maybe_proposal: Option<&ProposalCall>,
     ) -> Result<Referendum, ReferendumImportError> {
         let snapshot_height = snapshot_height - 50;
         log::info!("Process {} referendum #{}.", chain.token_ticker, index,);
@@ -88,6 +93,7 @@ impl ReferendumImporter {
                 &self.config,
                 &referendum,
                 preimage_exists,
+                maybe_proposal,
                 &new_opensquare_proposal_response,
             )
             .await
@@ -98,6 +104,7 @@ impl ReferendumImporter {
                 chain.id,
                 &referendum,
                 preimage_exists,
+                maybe_preimage_lookup,
                 &new_opensquare_proposal_response.cid,
                 &new_opensquare_proposal_response.post_uid,
                 self.config.telegram.chat_id,
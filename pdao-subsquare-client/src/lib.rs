@@ -1,6 +1,7 @@
 use chrono::Utc;
 use num_ordinal::{Ordinal, O32};
 use pdao_config::Config;
+use pdao_keystore::{Keystore, GOV_KEY_NAME};
 use pdao_types::governance::policy::PolicyEvaluation;
 use pdao_types::governance::subsquare::{
     SubSquareCommentData, SubSquareCommentIndexerData, SubSquareCommentReplyData,
@@ -8,8 +9,7 @@ use pdao_types::governance::subsquare::{
     SubSquareReferendum, SubSquareReferendumList,
 };
 use pdao_types::substrate::chain::Chain;
-use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
-use sp_core::{sr25519, Pair};
+use std::sync::Arc;
 
 #[allow(clippy::too_many_arguments)]
 fn get_vote_content(
@@ -23,15 +23,7 @@ fn get_vote_content(
     feedback_summary: &str,
     delegation_address: &str,
 ) -> anyhow::Result<String> {
-    let vote = if let Some(vote) = evaluation.simplify()? {
-        if vote {
-            "AYE"
-        } else {
-            "NAY"
-        }
-    } else {
-        "ABSTAIN"
-    };
+    let vote = evaluation.on_chain_vote()?.to_string();
 
     let coi_disclaimer = ""; /*if has_coi {
         "<br><br>**DISCLAIMER:** Our Decentralized Voices delegation voted to abstain on this referendum in accordance with our conflict of interest policy, [announced](https://x.com/PermanenceDAO/status/1905223487976783987) on March 27th, 2025."
@@ -70,12 +62,14 @@ Kind regards,<br>Permanence DAO<br>💬 [Public Telegram](https://t.me/permanenc
 pub struct SubSquareClient {
     config: Config,
     http_client: reqwest::Client,
+    keystore: Arc<Keystore>,
 }
 
 impl SubSquareClient {
-    pub fn new(config: &Config) -> anyhow::Result<Self> {
+    pub fn new(config: &Config, keystore: Arc<Keystore>) -> anyhow::Result<Self> {
         Ok(Self {
             config: config.clone(),
+            keystore,
             http_client: reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(
                     config.http.request_timeout_seconds,
@@ -120,41 +114,36 @@ impl SubSquareClient {
             .await?)
     }
 
-    fn get_address(&self, chain: &Chain) -> String {
-        let pair = sr25519::Pair::from_string(&self.config.substrate.gov_proxy_seed_phrase, None)
-            .expect("Invalid seed phrase");
-        pair.public()
-            .to_ss58check_with_version(Ss58AddressFormat::from(chain.ss58_prefix))
+    fn get_address(&self, chain: &Chain) -> anyhow::Result<String> {
+        Ok(self
+            .keystore
+            .get_signer(GOV_KEY_NAME)?
+            .account_id()
+            .to_ss58_check_with_version(chain.ss58_prefix))
     }
 
-    fn sign(&self, data: &[u8]) -> String {
-        let pair = sr25519::Pair::from_string(&self.config.substrate.gov_proxy_seed_phrase, None)
-            .expect("Invalid seed phrase");
-        let signature = pair.sign(data);
-        format!("0x{}", hex::encode(signature))
+    fn sign(&self, data: &[u8]) -> anyhow::Result<String> {
+        let signature = self.keystore.get_signer(GOV_KEY_NAME)?.sign(data);
+        Ok(format!("0x{}", hex::encode(signature)))
     }
 
+    /// Renders the comment of the DAO's vote on a referendum.
     #[allow(clippy::too_many_arguments)]
-    pub async fn post_comment(
+    pub fn get_vote_comment_content(
         &self,
         chain: &Chain,
-        referendum: &SubSquareReferendum,
         cid: &str,
         previous_vote_count: u32,
         evaluation: &PolicyEvaluation,
         description_lines: &[String],
         has_coi: bool,
         feedback_summary: &str,
-    ) -> anyhow::Result<SubSquareCommentResponse> {
-        let url = format!(
-            "https://{}-api.subsquare.io/sima/referenda/{}/comments",
-            chain.chain, referendum.referendum_index,
-        );
+    ) -> anyhow::Result<String> {
         let delegation_address = match chain.chain.as_str() {
             "polkadot" => self.config.voter.polkadot_real_account_address.as_str(),
             _ => self.config.voter.kusama_real_account_address.as_str(),
         };
-        let content = get_vote_content(
+        get_vote_content(
             chain,
             &self.config.voter.voting_policy_version,
             cid,
@@ -164,24 +153,37 @@ impl SubSquareClient {
             has_coi,
             feedback_summary,
             delegation_address,
-        )?;
+        )
+    }
+
+    pub async fn post_comment(
+        &self,
+        chain: &Chain,
+        referendum_index: u32,
+        proposed_height: u64,
+        content: &str,
+    ) -> anyhow::Result<SubSquareCommentResponse> {
+        let url = format!(
+            "https://{}-api.subsquare.io/sima/referenda/{referendum_index}/comments",
+            chain.chain,
+        );
         let request_data = SubSquareCommentData {
             action: "comment".to_string(),
             indexer: SubSquareCommentIndexerData {
                 pallet: "referenda".to_string(),
                 object: "referendumInfoFor".to_string(),
-                proposed_height: referendum.extrinsic.block_number,
-                id: referendum.referendum_index,
+                proposed_height,
+                id: referendum_index,
             },
-            content,
+            content: content.to_string(),
             content_format: "subsquare_md".to_string(),
             timestamp: Utc::now().timestamp_millis() as u64,
         };
         let request_data_json = serde_json::to_string(&request_data)?;
-        let signature_hex = self.sign(&request_data_json.into_bytes());
+        let signature_hex = self.sign(&request_data_json.into_bytes())?;
         let request = SubSquareCommentRequest {
             entity: request_data,
-            address: self.get_address(chain),
+            address: self.get_address(chain)?,
             signature: signature_hex,
             signer_wallet: "polkadot-js".to_string(),
         };
@@ -189,79 +191,48 @@ impl SubSquareClient {
         let response = match response_result {
             Ok(response) => response,
             Err(error) => {
-                log::error!(
-                    "Error while posting SubSquare comment vote #{}: {}",
-                    previous_vote_count + 1,
-                    error
-                );
+                log::error!("Error while posting SubSquare comment: {error}");
                 return Err(error.into());
             }
         };
         let status_code = response.status();
         let response_text = response.text().await?;
         if !status_code.is_success() {
-            let error_message = format!(
-                "Error while posting SubSquare comment for vote #{}: {}",
-                previous_vote_count + 1,
-                response_text
-            );
+            let error_message = format!("Error while posting SubSquare comment: {response_text}");
             log::error!("{error_message}");
             return Err(anyhow::Error::msg(error_message));
         }
         log::info!(
-            "Posted SubSquare comment for {} referendum #{} vote #{}. Response: {response_text}",
+            "Posted SubSquare comment for {} referendum #{referendum_index}. Response: {response_text}",
             chain.token_ticker,
-            referendum.referendum_index,
-            previous_vote_count + 1,
         );
 
         Ok(serde_json::from_str(&response_text)?)
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub async fn post_comment_reply(
         &self,
         chain: &Chain,
-        referendum: &SubSquareReferendum,
-        cid: &str,
+        referendum_index: u32,
         comment_cid: &str,
-        previous_vote_count: u32,
-        evaluation: &PolicyEvaluation,
-        description_lines: &[String],
-        has_coi: bool,
-        feedback_summary: &str,
+        content: &str,
     ) -> anyhow::Result<SubSquareCommentResponse> {
         let url = format!(
-            "https://{}-api.subsquare.io/sima/referenda/{}/comments/{comment_cid}/replies",
-            chain.chain, referendum.referendum_index,
+            "https://{}-api.subsquare.io/sima/referenda/{referendum_index}/comments/{comment_cid}/replies",
+            chain.chain,
         );
-        let delegation_address = match chain.chain.as_str() {
-            "polkadot" => self.config.voter.polkadot_real_account_address.as_str(),
-            _ => self.config.voter.kusama_real_account_address.as_str(),
-        };
-        let content = get_vote_content(
-            chain,
-            &self.config.voter.voting_policy_version,
-            cid,
-            previous_vote_count,
-            evaluation,
-            description_lines,
-            has_coi,
-            feedback_summary,
-            delegation_address,
-        )?;
         let request_data = SubSquareCommentReplyData {
             action: "comment".to_string(),
             comment_cid: comment_cid.to_string(),
-            content,
+            content: content.to_string(),
             content_format: "subsquare_md".to_string(),
             timestamp: Utc::now().timestamp_millis() as u64,
         };
         let request_data_json = serde_json::to_string(&request_data)?;
-        let signature_hex = self.sign(&request_data_json.into_bytes());
+        let signature_hex = self.sign(&request_data_json.into_bytes())?;
         let request = SubSquareCommentReplyRequest {
             entity: request_data,
-            address: self.get_address(chain),
+            address: self.get_address(chain)?,
             signature: signature_hex,
             signer_wallet: "polkadot-js".to_string(),
         };
@@ -282,9 +253,8 @@ impl SubSquareClient {
             return Err(anyhow::Error::msg(error_message));
         }
         log::info!(
-            "Posted SubSquare comment reply for {} referendum ${}. Response: {response_text}",
+            "Posted SubSquare comment reply for {} referendum #{referendum_index}. Response: {response_text}",
             chain.token_ticker,
-            referendum.referendum_index,
         );
 
         Ok(serde_json::from_str(&response_text)?)
@@ -12,6 +12,7 @@ use pdao_types::substrate::balance::Balance;
 use pdao_types::substrate::block::BlockHeader;
 use pdao_types::substrate::chain::Chain;
 use pdao_types::substrate::identity::{Identity, SubIdentity};
+use pdao_types::substrate::payment::RuntimeDispatchInfo;
 use pdao_types::substrate::{AccountData, AccountInfo};
 use rustc_hash::FxHashMap as HashMap;
 use sp_core::storage::StorageChangeSet;
@@ -152,6 +153,14 @@ impl SubstrateClient {
         Ok(header)
     }
 
+    pub async fn get_partial_fee(&self, extrinsic_hex: &str) -> anyhow::Result<u128> {
+        let dispatch_info: RuntimeDispatchInfo = self
+            .ws_client
+            .request("payment_queryInfo", rpc_params!(extrinsic_hex))
+            .await?;
+        dispatch_info.get_partial_fee()
+    }
+
     pub async fn get_identities(&self, at: &str) -> anyhow::Result<Vec<Identity>> {
         let keys = self
             .get_all_keys_for_storage("Identity", "IdentityOf", at)
@@ -1,4 +1,4 @@
-use crate::command::util::{require_thread, require_voting_admin};
+use crate::command::util::require_thread;
 use crate::{TelegramBot, CONFIG};
 use pdao_types::substrate::chain::Chain;
 
@@ -7,16 +7,21 @@ impl TelegramBot {
         &self,
         chat_id: i64,
         thread_id: Option<i32>,
-        username: &str,
     ) -> anyhow::Result<()> {
-        require_voting_admin(username)?;
         let thread_id = require_thread(thread_id)?;
-        let (maybe_referendum_id, maybe_title) = if let Some(db_referendum) = self
+        // a referendum topic is locked until it's archived
+        let (_maybe_lock, maybe_referendum_id, maybe_title) = if self
             .postgres
             .get_referendum_by_telegram_chat_and_thread_id(chat_id, thread_id)
             .await?
+            .is_some()
         {
+            let Some((lock, db_referendum)) = self.lock_db_referendum(chat_id, thread_id).await?
+            else {
+                return Ok(());
+            };
             (
+                Some(lock),
                 Some(db_referendum.id),
                 Some(format!(
                     "[{}] {} #{} - {}",
@@ -27,7 +32,7 @@ impl TelegramBot {
                 )),
             )
         } else {
-            (None, None)
+            (None, None, None)
         };
 
         use std::fs;
@@ -0,0 +1,93 @@
+use crate::command::util::require_thread;
+use crate::TelegramBot;
+
+const AUDIT_LOG_ENTRY_COUNT: u32 = 20;
+
+impl TelegramBot {
+    /// Records a command or automated action in the audit log, on the referendum of the topic
+    /// when there's one. Failing to record is logged and doesn't fail the action.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn save_audit_log_entry<T>(
+        &self,
+        actor: &str,
+        actor_telegram_user_id: Option<i64>,
+        command: &str,
+        arguments: &[String],
+        chat_id: i64,
+        thread_id: Option<i32>,
+        result: &anyhow::Result<T>,
+    ) {
+        let save_result = async {
+            let referendum_id = match thread_id {
+                Some(thread_id) => self
+                    .postgres
+                    .get_referendum_by_telegram_chat_and_thread_id(chat_id, thread_id)
+                    .await?
+                    .map(|db_referendum| db_referendum.id),
+                None => None,
+            };
+            self.postgres
+                .save_audit_log_entry(
+                    actor,
+                    actor_telegram_user_id,
+                    command,
+                    arguments,
+                    referendum_id,
+                    Some(chat_id),
+                    thread_id,
+                    result
+                        .as_ref()
+                        .err()
+                        .map(|error| error.to_string())
+                        .as_deref(),
+                )
+                .await
+        }
+        .await;
+        if let Err(error) = save_result {
+            log::error!("Cannot save audit log entry for {actor} {command}: {error}");
+        }
+    }
+
+    pub(crate) async fn process_audit_command(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+    ) -> anyhow::Result<()> {
+        let thread_id = require_thread(thread_id)?;
+        let entries = self
+            .postgres
+            .get_thread_audit_log_entries(chat_id, thread_id, AUDIT_LOG_ENTRY_COUNT)
+            .await?;
+        let message = if entries.is_empty() {
+            "No audit log entries for this topic.".to_string()
+        } else {
+            let lines: Vec<String> = entries
+                .iter()
+                .rev()
+                .map(|entry| {
+                    let outcome = match entry.error.as_ref() {
+                        None => "✅".to_string(),
+                        Some(error) => format!("❌ {error}"),
+                    };
+                    format!(
+                        "{} {} {} {} {outcome}",
+                        entry.created_at.format("%Y-%m-%d %H:%M"),
+                        entry.actor,
+                        entry.command,
+                        entry.arguments.join(" "),
+                    )
+                })
+                .collect();
+            format!(
+                "🧾 Last {} actions on this topic:\n```\n{}\n```",
+                entries.len(),
+                lines.join("\n").replace('`', "'"),
+            )
+        };
+        self.telegram_client
+            .send_message(chat_id, Some(thread_id), &message, false)
+            .await?;
+        Ok(())
+    }
+}
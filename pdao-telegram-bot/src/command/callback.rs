@@ -0,0 +1,89 @@
+use crate::command::util::get_user_display_name;
+use crate::command::{get_command_role, requires_confirmation};
+use crate::{TelegramBot, CONFIG};
+use frankenstein::types::{CallbackQuery, MaybeInaccessibleMessage};
+use pdao_telegram_client::{
+    get_confirmation_keyboard, get_referendum_keyboard_button_text, CANCEL_CALLBACK_DATA,
+    CONFIRM_CALLBACK_PREFIX,
+};
+
+impl TelegramBot {
+    /// Runs the command of a pressed inline keyboard button. Commands that require confirmation
+    /// first get a confirmation message, and run when its confirm button is pressed.
+    pub(crate) async fn process_callback_query(
+        &self,
+        callback_query: &CallbackQuery,
+    ) -> anyhow::Result<()> {
+        let Some(MaybeInaccessibleMessage::Message(message)) = &callback_query.message else {
+            return Err(anyhow::anyhow!("This message is no longer available."));
+        };
+        if message.chat.id != CONFIG.telegram.chat_id {
+            return Ok(());
+        }
+        let data = callback_query.data.as_deref().unwrap_or_default();
+        let chat_id = message.chat.id;
+        let thread_id = message.message_thread_id;
+        let user = &callback_query.from;
+        let display_name = get_user_display_name(user);
+        if data == CANCEL_CALLBACK_DATA {
+            self.telegram_client
+                .remove_reply_markup(chat_id, message.message_id)
+                .await?;
+            self.telegram_client
+                .answer_callback_query(&callback_query.id, Some("Cancelled."), false)
+                .await?;
+            return Ok(());
+        }
+        let (command, is_confirmed) = match data.strip_prefix(CONFIRM_CALLBACK_PREFIX) {
+            Some(command) => (command, true),
+            None => (data, false),
+        };
+        let Some(button_text) = get_referendum_keyboard_button_text(command) else {
+            return Err(anyhow::anyhow!("Unknown button."));
+        };
+        log::info!(
+            "Button {command} pressed by {display_name} in chat {chat_id} thread {thread_id:?}."
+        );
+        if requires_confirmation(command) && !is_confirmed {
+            if let Some(role) = get_command_role(command) {
+                self.require_role(user.id as i64, role).await?;
+            }
+            self.telegram_client
+                .send_message_with_reply_markup(
+                    chat_id,
+                    thread_id,
+                    &format!(
+                        "⚠️ {}, please confirm *{button_text}*.",
+                        display_name.replace('_', "\\_"),
+                    ),
+                    false,
+                    Some(get_confirmation_keyboard(command)),
+                )
+                .await?;
+            self.telegram_client
+                .answer_callback_query(&callback_query.id, None, false)
+                .await?;
+            return Ok(());
+        }
+        if is_confirmed {
+            self.telegram_client
+                .remove_reply_markup(chat_id, message.message_id)
+                .await?;
+        }
+        self.telegram_client
+            .answer_callback_query(&callback_query.id, None, false)
+            .await?;
+        // the query is answered before running the command, so errors go to the topic
+        if let Err(error) = self
+            .process_command(chat_id, thread_id, user, command, &[])
+            .await
+        {
+            let message = format!("Error while processing {command} button: {error:?}");
+            log::error!("{message}");
+            self.telegram_client
+                .send_message(chat_id, thread_id, &message, true)
+                .await?;
+        }
+        Ok(())
+    }
+}
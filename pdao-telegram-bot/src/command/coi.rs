@@ -1,7 +1,6 @@
 use crate::command::util::{
-    require_db_referendum, require_db_referendum_is_active, require_opensquare_referendum,
-    require_subsquare_referendum, require_subsquare_referendum_active, require_thread,
-    require_voting_admin,
+    require_db_referendum_is_active, require_opensquare_referendum, require_subsquare_referendum,
+    require_subsquare_referendum_active, require_thread,
 };
 use crate::TelegramBot;
 use pdao_types::substrate::chain::Chain;
@@ -12,11 +11,13 @@ impl TelegramBot {
         chat_id: i64,
         thread_id: Option<i32>,
         has_coi: bool,
-        username: &str,
     ) -> anyhow::Result<()> {
-        require_voting_admin(username)?;
         let thread_id = require_thread(thread_id)?;
-        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        // votes read the conflict of interest while they're being prepared
+        let Some((_lock, db_referendum)) = self.lock_db_referendum(chat_id, thread_id).await?
+        else {
+            return Ok(());
+        };
         require_db_referendum_is_active(&db_referendum)?;
         if has_coi && db_referendum.has_coi {
             self.telegram_client
@@ -66,9 +67,9 @@ impl TelegramBot {
             )
             .await?;
         let message = if has_coi {
-            "Referendum has been marked for conlict of interest.\nDV delegation account will vote abstain on this referendum."
+            "Referendum has been marked for conlict of interest.\nVoting accounts will follow their conflict of interest behavior on this referendum."
         } else {
-            "Conlict of interest has been removed from the referendum.\nDV delegation account will vote normally on this referendum."
+            "Conlict of interest has been removed from the referendum.\nAll voting accounts will vote normally on this referendum."
         };
         self.telegram_client
             .send_message(chat_id, Some(thread_id), message, true)
@@ -0,0 +1,72 @@
+use crate::command::util::{require_db_referendum, require_thread};
+use crate::TelegramBot;
+use pdao_telegram_client::format_proposal;
+use pdao_types::substrate::chain::Chain;
+use pdao_types::substrate::referendum::{ProposalCall, ReferendumLookup};
+
+impl TelegramBot {
+    /// Whether the preimage of the referendum lookup exists, and its decoded call tree when it
+    /// decodes.
+    pub(crate) async fn get_referendum_proposal(
+        &self,
+        chain: &Chain,
+        maybe_lookup: Option<&ReferendumLookup>,
+    ) -> anyhow::Result<(bool, Option<ProposalCall>)> {
+        let Some(lookup) = maybe_lookup else {
+            return Ok((false, None));
+        };
+        let Some(preimage) = self.voter.get_preimage(chain, lookup).await? else {
+            return Ok((false, None));
+        };
+        match self.voter.decode_preimage(chain, &preimage).await {
+            Ok(proposal) => Ok((true, Some(proposal))),
+            Err(error) => {
+                log::warn!(
+                    "Cannot decode {} preimage 0x{}: {error:?}",
+                    chain.display,
+                    hex::encode(lookup.hash),
+                );
+                Ok((true, None))
+            }
+        }
+    }
+
+    pub(crate) async fn process_decode_command(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+    ) -> anyhow::Result<()> {
+        let thread_id = require_thread(thread_id)?;
+        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        let chain = Chain::from_id(db_referendum.network_id);
+        let Some(lookup) = self
+            .voter
+            .get_referendum_lookup(&chain, db_referendum.index)
+            .await?
+        else {
+            self.telegram_client
+                .send_message(
+                    chat_id,
+                    Some(thread_id),
+                    "Referendum proposal not found on chain.",
+                    false,
+                )
+                .await?;
+            return Ok(());
+        };
+        let Some(preimage) = self.voter.get_preimage(&chain, &lookup).await? else {
+            self.telegram_client
+                .send_message(chat_id, Some(thread_id), "⚠️ No preimage.", false)
+                .await?;
+            return Ok(());
+        };
+        let message = match self.voter.decode_preimage(&chain, &preimage).await {
+            Ok(proposal) => format!("*Proposal:*\n{}", format_proposal(&proposal)),
+            Err(error) => format!("Cannot decode the preimage:\n```\n{error}\n```"),
+        };
+        self.telegram_client
+            .send_message(chat_id, Some(thread_id), &message, false)
+            .await?;
+        Ok(())
+    }
+}
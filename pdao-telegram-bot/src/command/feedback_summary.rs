@@ -1,9 +1,9 @@
 use crate::command::util::{
-    get_vote_counts, require_db_referendum, require_db_referendum_is_active,
+    get_blocks_left, get_vote_counts, require_db_referendum, require_db_referendum_is_active,
     require_opensquare_votes, require_subsquare_referendum, require_thread,
 };
 use crate::TelegramBot;
-use pdao_types::governance::policy::{Policy, PolicyEvaluation};
+use pdao_types::governance::policy::PolicyEvaluation;
 use pdao_types::substrate::chain::Chain;
 
 impl TelegramBot {
@@ -17,7 +17,7 @@ impl TelegramBot {
         let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
         require_db_referendum_is_active(&db_referendum)?;
         let chain = Chain::from_id(db_referendum.network_id);
-        let voting_member_count = self.postgres.get_all_members(false).await?.len() as u32;
+        let members = self.postgres.get_all_members(true).await?;
         let subsquare_referendum =
             require_subsquare_referendum(&self.subsquare_client, &chain, db_referendum.index)
                 .await?;
@@ -55,9 +55,10 @@ impl TelegramBot {
                 .await?;
             return Ok(());
         }
-        let vote_counts = get_vote_counts(voting_member_count, &opensquare_votes);
-        let voting_policy = Policy::policy_for_track(&db_referendum.track);
-        let (evaluation, _) = voting_policy.evaluate(&vote_counts);
+        let vote_counts = get_vote_counts(&members, &opensquare_votes);
+        let voting_policy = self.voting_policy.policy_for_track(&db_referendum.track);
+        let (evaluation, _) =
+            voting_policy.evaluate(&vote_counts, get_blocks_left(&subsquare_referendum));
         if let PolicyEvaluation::ParticipationNotMet {
             participation_threshold,
             ..
@@ -1,9 +1,12 @@
 use crate::command::util::{
-    require_db_referendum, require_db_referendum_is_active, require_opensquare_referendum,
-    require_subsquare_referendum, require_subsquare_referendum_active, require_thread,
-    require_voting_admin,
+    format_account_votes, format_dry_run, require_db_referendum_is_active,
+    require_opensquare_referendum, require_subsquare_referendum,
+    require_subsquare_referendum_active, require_thread,
 };
 use crate::TelegramBot;
+use pdao_types::governance::opensquare::OpenSquareReferendum;
+use pdao_types::governance::{OnChainVote, Referendum, VoteIntentRecord};
+use pdao_types::outbox::OutboxEffect;
 use pdao_types::substrate::chain::Chain;
 
 impl TelegramBot {
@@ -11,12 +14,13 @@ impl TelegramBot {
         &self,
         chat_id: i64,
         thread_id: Option<i32>,
-        username: &str,
         vote: Option<bool>,
     ) -> anyhow::Result<()> {
-        require_voting_admin(username)?;
         let thread_id = require_thread(thread_id)?;
-        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        let Some((_lock, db_referendum)) = self.lock_db_referendum(chat_id, thread_id).await?
+        else {
+            return Ok(());
+        };
         require_db_referendum_is_active(&db_referendum)?;
         let chain = Chain::from_id(db_referendum.network_id);
         let subsquare_referendum =
@@ -48,50 +52,101 @@ impl TelegramBot {
             chain.chain,
             db_referendum.index
         );
-        let balance = 10u128.pow(chain.token_decimals as u32);
-        let conviction = 1;
-        log::info!("Submit vote.");
-        let (block_hash, block_number, extrinsic_index) = self
+        let policy = self.voting_policy.policy_for_track(&db_referendum.track);
+        let account_votes = self
             .voter
-            .vote(
+            .get_account_votes(
                 &chain,
-                db_referendum.index,
+                db_referendum.track.id(),
                 db_referendum.has_coi,
-                vote,
-                balance,
+                OnChainVote::from(vote),
+                &policy.vote_balance(),
+            )
+            .await?;
+        let balance = account_votes
+            .iter()
+            .map(|account_vote| account_vote.balance)
+            .sum();
+        let conviction = policy.conviction();
+        if self.voter.is_safe_mode() {
+            let dry_run = self
+                .voter
+                .dry_run_vote(&chain, db_referendum.index, &account_votes, conviction)
+                .await?;
+            let message = format!(
+                "🧪 Safe mode is on, simulated the force vote instead.\n{}",
+                format_dry_run(&chain, &dry_run),
+            );
+            self.telegram_client
+                .send_message(chat_id, Some(thread_id), &message, true)
+                .await?;
+            return Ok(());
+        }
+        let vote_intent_record = VoteIntentRecord {
+            vote,
+            split: None,
+            balance,
+            conviction,
+            has_coi: db_referendum.has_coi,
+            is_forced: true,
+            post_feedback: false,
+            policy_record: None,
+            account_votes: account_votes.clone(),
+            member_votes: Vec::new(),
+        };
+        let (vote_intent_id, block_number, extrinsic_index) = self
+            .submit_vote_intent(
+                &chain,
+                &db_referendum,
+                &vote_intent_record,
+                &account_votes,
                 conviction,
             )
             .await?;
         log::info!("Save vote in DB.");
         let vote_id = self
             .postgres
-            .save_vote(
-                db_referendum.network_id,
-                db_referendum.id,
-                db_referendum.index,
-                &block_hash,
-                block_number,
-                extrinsic_index,
-                vote,
-                balance,
-                conviction,
-                None,
-                None,
-                db_referendum.has_coi,
-                true,
-            )
-            .await?;
-        self.postgres
-            .set_referendum_last_vote_id(db_referendum.id, Some(vote_id as u32))
-            .await?;
+            .record_vote(vote_intent_id, None, None)
+            .await? as u32;
+        let link = format!(
+            "https://{}.subscan.io/extrinsic/{}-{}",
+            chain.chain.to_lowercase(),
+            block_number,
+            extrinsic_index,
+        );
+        self.enqueue_force_vote_posts(
+            &chain,
+            &db_referendum,
+            &opensquare_referendum,
+            vote_id,
+            &vote_intent_record,
+            &link,
+        )
+        .await?;
+        self.deliver_outbox_items().await
+    }
+
+    /// Leaves the topic name, the OpenSquare appendant and the topic message of a recorded
+    /// force vote to the outbox.
+    pub(crate) async fn enqueue_force_vote_posts(
+        &self,
+        chain: &Chain,
+        db_referendum: &Referendum,
+        opensquare_referendum: &OpenSquareReferendum,
+        vote_id: u32,
+        record: &VoteIntentRecord,
+        link: &str,
+    ) -> anyhow::Result<()> {
+        let chat_id = db_referendum.telegram_chat_id;
+        let thread_id = db_referendum.telegram_topic_id;
         let current_vote_count = self
             .postgres
             .get_referendum_vote_count(db_referendum.id)
             .await?;
         let message = format!(
-            "**Vote #{}: FORCE-{}**\nhttps://{}.subscan.io/extrinsic/{}-{}",
+            "**Vote #{}: FORCE-{}**\n{}\n{link}",
             current_vote_count,
-            (if let Some(vote) = vote {
+            (if let Some(vote) = record.vote {
                 if vote {
                     "AYE"
                 } else {
@@ -102,26 +157,40 @@ impl TelegramBot {
             })
             .to_string()
             .to_uppercase(),
-            chain.chain.to_lowercase(),
-            block_number,
-            extrinsic_index,
+            format_account_votes(chain, &record.account_votes),
         );
-        self.telegram_client
-            .update_referendum_topic_name(
-                chat_id,
-                thread_id,
-                &opensquare_referendum.title,
-                db_referendum.has_coi,
-                None,
-                &format!("V{current_vote_count}"),
-                db_referendum.status.get_status_icon(),
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{vote_id}-topic-name"),
+                &OutboxEffect::TelegramTopicName {
+                    chat_id,
+                    thread_id,
+                    name: opensquare_referendum.title.clone(),
+                    has_coi: record.has_coi,
+                    vote_count_status: format!("V{current_vote_count}"),
+                    status_emoji: db_referendum.status.get_status_icon().to_string(),
+                },
             )
             .await?;
-        self.opensquare_client
-            .make_appendant_on_proposal(&chain, &db_referendum.opensquare_cid, &message)
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{vote_id}-opensquare-appendant"),
+                &OutboxEffect::OpenSquareAppendant {
+                    network_id: db_referendum.network_id,
+                    cid: db_referendum.opensquare_cid.clone(),
+                    content: message.clone(),
+                },
+            )
             .await?;
-        self.telegram_client
-            .send_message(chat_id, Some(thread_id), &message, true)
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{vote_id}-message"),
+                &OutboxEffect::TelegramMessage {
+                    chat_id,
+                    thread_id: Some(thread_id),
+                    message,
+                },
+            )
             .await?;
         Ok(())
     }
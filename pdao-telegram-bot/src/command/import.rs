@@ -58,13 +58,20 @@ impl TelegramBot {
                 .await?;
             return Ok(());
         };
-        let preimage_exists = match self.voter.get_referendum_lookup(&chain, index).await? {
-            Some(lookup) => self.voter.get_preimage(&chain, &lookup).await?.is_some(),
-            None => false,
-        };
+        let maybe_preimage_lookup = self.voter.get_referendum_lookup(&chain, index).await?;
+        let (preimage_exists, maybe_proposal) = self
+            .get_referendum_proposal(&chain, maybe_preimage_lookup.as_ref())
+            .await?;
         if let Err(error) = self
             .referendum_importer
-            .import_referendum(&chain, index, polkadot_snapshot_height, preimage_exists)
+            .import_referendum(
+                &chain,
+                index,
+                polkadot_snapshot_height,
+                preimage_exists,
+                maybe_preimage_lookup.as_ref(),
+                maybe_proposal.as_ref(),
+            )
             .await
         {
             let message = match error {
@@ -1,5 +1,10 @@
+use pdao_types::Role;
+
 pub mod archive;
+pub mod audit;
+pub mod callback;
 pub mod coi;
+pub mod decode;
 pub mod feedback_summary;
 pub mod force_vote;
 pub mod import;
@@ -7,8 +12,61 @@ pub mod mark_leave;
 pub mod mark_return;
 pub mod member_list;
 pub mod notify;
+pub mod reconcile;
+pub mod reevaluate;
 pub mod remove_vote;
+pub mod role;
+pub mod signature;
+pub mod simulate_vote;
 pub mod status;
 pub mod terminate;
 pub mod util;
 pub mod vote;
+
+/// Role required to call each command, the others are open to everyone in the chat.
+pub(crate) fn get_command_role(command: &str) -> Option<Role> {
+    match command {
+        "/grant" | "/revoke" => Some(Role::Admin),
+        "/archive"
+        | "/forceabstain"
+        | "/forceaye"
+        | "/forcenay"
+        | "/import"
+        | "/notify"
+        | "/reconcile"
+        | "/reevaluate"
+        | "/removecoi"
+        | "/removevote"
+        | "/reportcoi"
+        | "/signature"
+        | "/simulatevote"
+        | "/terminate"
+        | "/timeout"
+        | "/vote"
+        | "/votewithoutfeedback" => Some(Role::VotingAdmin),
+        "/leave" | "/return" => Some(Role::Member),
+        "/audit" => Some(Role::Observer),
+        _ => None,
+    }
+}
+
+/// Commands that submit an extrinsic, which waits for a signature with offline signing.
+pub(crate) fn may_wait_for_signature(command: &str) -> bool {
+    matches!(
+        command,
+        "/forceabstain"
+            | "/forceaye"
+            | "/forcenay"
+            | "/removevote"
+            | "/vote"
+            | "/votewithoutfeedback"
+    )
+}
+
+/// Commands that need a second press on a confirmation button when run from a button.
+pub(crate) fn requires_confirmation(command: &str) -> bool {
+    matches!(
+        command,
+        "/forceabstain" | "/forceaye" | "/forcenay" | "/terminate"
+    )
+}
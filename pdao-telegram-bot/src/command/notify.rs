@@ -1,6 +1,6 @@
 use crate::command::util::{
     require_db_referendum, require_db_referendum_is_active, require_opensquare_votes,
-    require_thread, require_voting_admin,
+    require_thread,
 };
 use crate::TelegramBot;
 use pdao_types::substrate::account_id::AccountId;
@@ -11,9 +11,7 @@ impl TelegramBot {
         &self,
         chat_id: i64,
         thread_id: Option<i32>,
-        username: &str,
     ) -> anyhow::Result<()> {
-        require_voting_admin(username)?;
         let thread_id = require_thread(thread_id)?;
         let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
         require_db_referendum_is_active(&db_referendum)?;
@@ -0,0 +1,206 @@
+use crate::command::util::{
+    format_conviction_vote, require_db_referendum_is_active, require_thread,
+};
+use crate::{TelegramBot, CONFIG};
+use pdao_types::governance::{AccountVote, ConvictionVote, OnChainVote, Referendum, Vote};
+use pdao_types::substrate::chain::Chain;
+use pdao_voter::ChainVotes;
+
+/// Outcome of comparing the last recorded vote of a referendum with the on-chain votes of the
+/// voting accounts.
+pub(crate) struct VoteReconciliation {
+    pub last_vote: Option<Vote>,
+    pub chain_votes: ChainVotes,
+    pub discrepancies: Vec<String>,
+}
+
+/// Per-account votes the DB expects on chain, empty when there's no vote or it was removed.
+fn get_expected_votes(chain: &Chain, last_vote: &Option<Vote>) -> Vec<(String, ConvictionVote)> {
+    let Some(last_vote) = last_vote.as_ref().filter(|last_vote| !last_vote.is_removed) else {
+        return Vec::new();
+    };
+    let conviction = last_vote.conviction as u8;
+    if last_vote.account_votes.is_empty() {
+        // votes recorded before the multi-account batch were cast by the real account only
+        return CONFIG
+            .voter
+            .get_network_config(&chain.chain)
+            .map(|network_config| {
+                vec![(
+                    network_config.real_account_address,
+                    ConvictionVote::new(last_vote.on_chain_vote(), last_vote.balance, conviction),
+                )]
+            })
+            .unwrap_or_default();
+    }
+    last_vote
+        .account_votes
+        .iter()
+        .map(|account_vote| {
+            (
+                account_vote.address.clone(),
+                ConvictionVote::new(account_vote.vote, account_vote.balance, conviction),
+            )
+        })
+        .collect()
+}
+
+impl TelegramBot {
+    pub(crate) async fn reconcile_votes(
+        &self,
+        chain: &Chain,
+        db_referendum: &Referendum,
+    ) -> anyhow::Result<VoteReconciliation> {
+        let last_vote = self
+            .postgres
+            .get_referendum_last_vote(db_referendum.id)
+            .await?;
+        let expected_votes = get_expected_votes(chain, &last_vote);
+        let mut account_addresses = self.voter.get_voting_account_addresses(chain)?;
+        for (address, _) in expected_votes.iter() {
+            if !account_addresses.contains(address) {
+                account_addresses.push(address.clone());
+            }
+        }
+        let chain_votes = self
+            .voter
+            .get_chain_votes(
+                chain,
+                db_referendum.track.id(),
+                db_referendum.index,
+                &account_addresses,
+            )
+            .await?;
+        let mut discrepancies = Vec::new();
+        for (address, chain_vote) in chain_votes.account_votes.iter() {
+            let expected_vote = expected_votes
+                .iter()
+                .find(|(expected_address, _)| expected_address == address)
+                .map(|(_, expected_vote)| *expected_vote);
+            let is_match = match (&expected_vote, chain_vote) {
+                (Some(expected_vote), Some(chain_vote)) => expected_vote.matches(chain_vote),
+                (None, None) => true,
+                _ => false,
+            };
+            if !is_match {
+                discrepancies.push(format!(
+                    "• `{address}`: recorded {}, on chain {}",
+                    format_conviction_vote(chain, &expected_vote),
+                    format_conviction_vote(chain, chain_vote),
+                ));
+            }
+        }
+        Ok(VoteReconciliation {
+            last_vote,
+            chain_votes,
+            discrepancies,
+        })
+    }
+
+    pub(crate) async fn process_reconcile_command(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+    ) -> anyhow::Result<()> {
+        let thread_id = require_thread(thread_id)?;
+        let Some((_lock, db_referendum)) = self.lock_db_referendum(chat_id, thread_id).await?
+        else {
+            return Ok(());
+        };
+        require_db_referendum_is_active(&db_referendum)?;
+        let chain = Chain::from_id(db_referendum.network_id);
+        let reconciliation = self.reconcile_votes(&chain, &db_referendum).await?;
+        if reconciliation.discrepancies.is_empty() {
+            self.telegram_client
+                .send_message(
+                    chat_id,
+                    Some(thread_id),
+                    "✅ The recorded vote matches the on-chain votes.",
+                    true,
+                )
+                .await?;
+            return Ok(());
+        }
+        let chain_account_votes: Vec<(String, ConvictionVote)> = reconciliation
+            .chain_votes
+            .account_votes
+            .iter()
+            .filter_map(|(address, chain_vote)| chain_vote.map(|vote| (address.clone(), vote)))
+            .collect();
+        let mut tx = self.postgres.begin_tx().await?;
+        let repair = if chain_account_votes.is_empty() {
+            if let Some(last_vote) = reconciliation.last_vote.as_ref() {
+                self.postgres
+                    .set_vote_removed(&mut tx, last_vote.id)
+                    .await?;
+            }
+            self.postgres
+                .set_referendum_last_vote_id(&mut tx, db_referendum.id, None)
+                .await?;
+            "Marked the recorded vote as removed.".to_string()
+        } else {
+            let mut conviction = 0;
+            let mut account_votes = Vec::new();
+            for (address, chain_vote) in chain_account_votes.iter() {
+                let (vote, balance, account_conviction) = chain_vote.to_on_chain_vote();
+                if let ConvictionVote::Standard { .. } = chain_vote {
+                    conviction = account_conviction;
+                }
+                account_votes.push(AccountVote {
+                    address: address.clone(),
+                    vote,
+                    balance,
+                    delegated_balance: 0,
+                });
+            }
+            let vote = account_votes[0].vote;
+            let (simple_vote, split) = match vote {
+                OnChainVote::Aye => (Some(true), None),
+                OnChainVote::Nay => (Some(false), None),
+                OnChainVote::Abstain => (None, None),
+                OnChainVote::Split(split) => (None, Some(split)),
+            };
+            let vote_id = self
+                .postgres
+                .save_vote(
+                    &mut tx,
+                    db_referendum.network_id,
+                    db_referendum.id,
+                    db_referendum.index,
+                    &reconciliation.chain_votes.block_hash,
+                    reconciliation.chain_votes.block_number,
+                    0,
+                    simple_vote,
+                    split,
+                    account_votes
+                        .iter()
+                        .map(|account_vote| account_vote.balance)
+                        .sum(),
+                    conviction,
+                    None,
+                    None,
+                    db_referendum.has_coi,
+                    true,
+                    None,
+                    &account_votes,
+                )
+                .await?;
+            self.postgres
+                .set_referendum_last_vote_id(&mut tx, db_referendum.id, Some(vote_id as u32))
+                .await?;
+            format!(
+                "Recorded the on-chain votes at block {} as a new vote.",
+                reconciliation.chain_votes.block_number,
+            )
+        };
+        self.postgres.commit_tx(tx).await?;
+        let message = format!(
+            "🔧 Repaired the vote record from the chain.\n{}\n{repair}",
+            reconciliation.discrepancies.join("\n"),
+        );
+        self.telegram_client
+            .send_message(chat_id, Some(thread_id), &message, true)
+            .await?;
+        Ok(())
+    }
+}
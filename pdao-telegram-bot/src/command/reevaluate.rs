@@ -0,0 +1,60 @@
+use crate::command::util::{require_db_referendum, require_thread};
+use crate::TelegramBot;
+
+impl TelegramBot {
+    pub(crate) async fn process_reevaluate_command(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+    ) -> anyhow::Result<()> {
+        let thread_id = require_thread(thread_id)?;
+        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        let votes = self.postgres.get_referendum_votes(db_referendum.id).await?;
+        if votes.is_empty() {
+            self.telegram_client
+                .send_message(
+                    chat_id,
+                    Some(thread_id),
+                    "No vote posted for this referendum yet.",
+                    true,
+                )
+                .await?;
+            return Ok(());
+        }
+        let mut lines = Vec::new();
+        for (i, vote) in votes.iter().enumerate() {
+            let recorded_vote = vote.on_chain_vote().to_string();
+            let Some(policy_record) = &vote.policy_record else {
+                lines.push(format!(
+                    "• Vote #{}: {}{} - no policy record",
+                    i + 1,
+                    if vote.is_forced { "FORCE-" } else { "" },
+                    recorded_vote,
+                ));
+                continue;
+            };
+            let (evaluation, _) = policy_record.reevaluate();
+            let reevaluated_vote = if evaluation.is_no_vote() {
+                "NO VOTE".to_string()
+            } else {
+                evaluation.on_chain_vote()?.to_string()
+            };
+            lines.push(format!(
+                "• Vote #{} ({}): recorded {} • re-evaluated {} {}",
+                i + 1,
+                policy_record.policy_version,
+                recorded_vote,
+                reevaluated_vote,
+                if recorded_vote == reevaluated_vote {
+                    "✓"
+                } else {
+                    "✘"
+                },
+            ));
+        }
+        self.telegram_client
+            .send_message(chat_id, Some(thread_id), &lines.join("\n"), true)
+            .await?;
+        Ok(())
+    }
+}
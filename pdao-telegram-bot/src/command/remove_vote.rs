@@ -1,9 +1,9 @@
 use crate::command::util::{
-    require_db_referendum, require_db_referendum_is_active, require_opensquare_referendum,
+    format_dry_run, require_db_referendum_is_active, require_opensquare_referendum,
     require_subsquare_referendum, require_subsquare_referendum_active, require_thread,
-    require_voting_admin,
 };
-use crate::TelegramBot;
+use crate::{TelegramBot, CONFIG};
+use pdao_types::outbox::OutboxEffect;
 use pdao_types::substrate::chain::Chain;
 
 impl TelegramBot {
@@ -11,11 +11,12 @@ impl TelegramBot {
         &self,
         chat_id: i64,
         thread_id: Option<i32>,
-        username: &str,
     ) -> anyhow::Result<()> {
-        require_voting_admin(username)?;
         let thread_id = require_thread(thread_id)?;
-        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        let Some((_lock, db_referendum)) = self.lock_db_referendum(chat_id, thread_id).await?
+        else {
+            return Ok(());
+        };
         require_db_referendum_is_active(&db_referendum)?;
         let last_vote_id = if let Some(last_vote_id) = db_referendum.last_vote_id {
             last_vote_id
@@ -38,6 +39,38 @@ impl TelegramBot {
             require_subsquare_referendum(&self.subsquare_client, &chain, db_referendum.index)
                 .await?;
         require_subsquare_referendum_active(&subsquare_referendum)?;
+        let last_vote = self
+            .postgres
+            .get_referendum_votes(db_referendum.id)
+            .await?
+            .into_iter()
+            .find(|vote| vote.id == last_vote_id);
+        // votes recorded before the multi-account batch were cast by the real account only
+        let account_addresses: Vec<String> = match last_vote {
+            Some(last_vote) if !last_vote.account_votes.is_empty() => last_vote
+                .account_votes
+                .iter()
+                .map(|account_vote| account_vote.address.clone())
+                .collect(),
+            _ => vec![match chain.chain.as_str() {
+                "polkadot" => CONFIG.voter.polkadot_real_account_address.clone(),
+                _ => CONFIG.voter.kusama_real_account_address.clone(),
+            }],
+        };
+        if self.voter.is_safe_mode() {
+            let dry_run = self
+                .voter
+                .dry_run_remove_vote(&chain, db_referendum.index, &account_addresses)
+                .await?;
+            let message = format!(
+                "🧪 Safe mode is on, simulated the vote removal instead.\n{}",
+                format_dry_run(&chain, &dry_run),
+            );
+            self.telegram_client
+                .send_message(chat_id, Some(thread_id), &message, true)
+                .await?;
+            return Ok(());
+        }
         self.telegram_client
             .send_message(
                 chat_id,
@@ -46,35 +79,57 @@ impl TelegramBot {
                 true,
             )
             .await?;
-        let (_block_hash, block_number, extrinsic_index) =
-            self.voter.remove_vote(&chain, db_referendum.index).await?;
+        let (_block_hash, block_number, extrinsic_index) = self
+            .voter
+            .remove_vote(&chain, db_referendum.index, &account_addresses)
+            .await?;
+        let mut tx = self.postgres.begin_tx().await?;
+        self.postgres
+            .set_referendum_last_vote_id(&mut tx, db_referendum.id, None)
+            .await?;
         self.postgres
-            .set_referendum_last_vote_id(db_referendum.id, None)
+            .set_vote_removed(&mut tx, last_vote_id)
             .await?;
-        self.postgres.set_vote_removed(last_vote_id).await?;
+        self.postgres.commit_tx(tx).await?;
         let message = format!(
             "Removed on-chain vote.\nhttps://{}.subscan.io/extrinsic/{}-{}",
             chain.chain.to_lowercase(),
             block_number,
             extrinsic_index
         );
-        self.opensquare_client
-            .make_appendant_on_proposal(&chain, &db_referendum.opensquare_cid, &message)
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{last_vote_id}-removal-opensquare-appendant"),
+                &OutboxEffect::OpenSquareAppendant {
+                    network_id: db_referendum.network_id,
+                    cid: db_referendum.opensquare_cid.clone(),
+                    content: message.clone(),
+                },
+            )
             .await?;
-        self.telegram_client
-            .send_message(chat_id, Some(thread_id), &message, true)
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{last_vote_id}-removal-message"),
+                &OutboxEffect::TelegramMessage {
+                    chat_id,
+                    thread_id: Some(thread_id),
+                    message,
+                },
+            )
             .await?;
-        self.telegram_client
-            .update_referendum_topic_name(
-                chat_id,
-                thread_id,
-                &opensquare_referendum.title,
-                db_referendum.has_coi,
-                None,
-                "VR",
-                db_referendum.status.get_status_icon(),
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{last_vote_id}-removal-topic-name"),
+                &OutboxEffect::TelegramTopicName {
+                    chat_id,
+                    thread_id,
+                    name: opensquare_referendum.title.clone(),
+                    has_coi: db_referendum.has_coi,
+                    vote_count_status: "VR".to_string(),
+                    status_emoji: db_referendum.status.get_status_icon().to_string(),
+                },
             )
             .await?;
-        Ok(())
+        self.deliver_outbox_items().await
     }
 }
@@ -0,0 +1,56 @@
+use crate::TelegramBot;
+use pdao_types::Role;
+use std::str::FromStr;
+
+impl TelegramBot {
+    pub(crate) async fn process_role_command(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+        user_id: i64,
+        display_name: &str,
+        args: &[String],
+        is_grant: bool,
+    ) -> anyhow::Result<()> {
+        let command = if is_grant { "grant" } else { "revoke" };
+        let [target_user_id, role] = args else {
+            self.telegram_client
+                .send_message(
+                    chat_id,
+                    thread_id,
+                    &format!("Usage: `/{command} <telegram_user_id> <observer|member|voting_admin|admin>`"),
+                    false,
+                )
+                .await?;
+            return Ok(());
+        };
+        let target_user_id: i64 = target_user_id
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid Telegram user id {target_user_id}."))?;
+        let role = Role::from_str(role)?;
+        let message = if is_grant {
+            if self
+                .postgres
+                .grant_user_role(target_user_id, role, user_id, display_name)
+                .await?
+            {
+                format!("✅ Granted the `{role}` role to user `{target_user_id}`.")
+            } else {
+                format!("User `{target_user_id}` already has the `{role}` role.")
+            }
+        } else if self
+            .postgres
+            .revoke_user_role(target_user_id, role, user_id, display_name)
+            .await?
+        {
+            format!("✅ Revoked the `{role}` role from user `{target_user_id}`.")
+        } else {
+            format!("User `{target_user_id}` doesn't have the `{role}` role.")
+        };
+        log::info!("{display_name} ({user_id}) {command}: {message}");
+        self.telegram_client
+            .send_message(chat_id, thread_id, &message, false)
+            .await?;
+        Ok(())
+    }
+}
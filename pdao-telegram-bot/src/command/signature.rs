@@ -0,0 +1,30 @@
+use crate::TelegramBot;
+
+impl TelegramBot {
+    pub(crate) async fn process_signature_command(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+        args: &[String],
+    ) -> anyhow::Result<()> {
+        let [request_id, signature_hex] = args else {
+            self.telegram_client
+                .send_message(
+                    chat_id,
+                    thread_id,
+                    "Usage: `/signature <request_id> <signature_hex>`",
+                    false,
+                )
+                .await?;
+            return Ok(());
+        };
+        let message = match self.voter.submit_signature(request_id, signature_hex) {
+            Ok(()) => format!("✅ Signature accepted for signing request `{request_id}`."),
+            Err(error) => format!("❌ Signature rejected:\n```\n{error}\n```"),
+        };
+        self.telegram_client
+            .send_message(chat_id, thread_id, &message, false)
+            .await?;
+        Ok(())
+    }
+}
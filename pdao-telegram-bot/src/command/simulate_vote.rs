@@ -0,0 +1,82 @@
+use crate::command::util::{
+    format_account_votes, format_dry_run, get_blocks_left, get_vote_counts, require_db_referendum,
+    require_db_referendum_is_active, require_opensquare_votes, require_subsquare_referendum,
+    require_subsquare_referendum_active, require_thread,
+};
+use crate::TelegramBot;
+use pdao_types::substrate::chain::Chain;
+
+impl TelegramBot {
+    pub(crate) async fn process_simulate_vote_command(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+    ) -> anyhow::Result<()> {
+        let thread_id = require_thread(thread_id)?;
+        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        require_db_referendum_is_active(&db_referendum)?;
+        let chain = Chain::from_id(db_referendum.network_id);
+        let members = self.postgres.get_all_members(true).await?;
+        let subsquare_referendum =
+            require_subsquare_referendum(&self.subsquare_client, &chain, db_referendum.index)
+                .await?;
+        require_subsquare_referendum_active(&subsquare_referendum)?;
+        let member_account_ids = self
+            .postgres
+            .get_all_member_account_ids_for_chain(true, Chain::polkadot().id)
+            .await?;
+        let opensquare_votes = require_opensquare_votes(
+            &self.opensquare_client,
+            &db_referendum.opensquare_cid,
+            &member_account_ids,
+        )
+        .await?;
+        let policy = self.voting_policy.policy_for_track(&db_referendum.track);
+        let vote_counts = get_vote_counts(&members, &opensquare_votes);
+        let blocks_left = get_blocks_left(&subsquare_referendum);
+        let (evaluation, description_lines) = policy.evaluate(&vote_counts, blocks_left);
+        if evaluation.is_no_vote() {
+            self.telegram_client
+                .send_message(
+                    chat_id,
+                    Some(thread_id),
+                    &format!(
+                        "🧪 Simulation: the outcome is no vote.\n{}",
+                        description_lines.join("\n"),
+                    ),
+                    true,
+                )
+                .await?;
+            return Ok(());
+        }
+        let account_votes = self
+            .voter
+            .get_account_votes(
+                &chain,
+                db_referendum.track.id(),
+                db_referendum.has_coi,
+                evaluation.on_chain_vote()?,
+                &policy.vote_balance(),
+            )
+            .await?;
+        let dry_run = self
+            .voter
+            .dry_run_vote(
+                &chain,
+                db_referendum.index,
+                &account_votes,
+                policy.conviction(),
+            )
+            .await?;
+        let message = format!(
+            "🧪 Simulated vote, nothing was submitted.\n{}\n{}\n{}",
+            description_lines.join("\n"),
+            format_account_votes(&chain, &account_votes),
+            format_dry_run(&chain, &dry_run),
+        );
+        self.telegram_client
+            .send_message(chat_id, Some(thread_id), &message, true)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,11 +1,10 @@
 use crate::command::util::{
-    get_vote_counts, require_db_referendum, require_db_referendum_is_active,
+    get_blocks_left, get_vote_counts, require_db_referendum, require_db_referendum_is_active,
     require_opensquare_referendum, require_opensquare_votes, require_subsquare_referendum,
     require_thread,
 };
 use crate::TelegramBot;
-use pdao_types::governance::policy::Policy;
-use pdao_types::governance::ReferendumStatus;
+use pdao_telegram_client::get_referendum_keyboard;
 use pdao_types::substrate::chain::Chain;
 
 impl TelegramBot {
@@ -18,7 +17,7 @@ impl TelegramBot {
         let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
         require_db_referendum_is_active(&db_referendum)?;
         let chain = Chain::from_id(db_referendum.network_id);
-        let voting_member_count = self.postgres.get_all_members(false).await?.len() as u32;
+        let members = self.postgres.get_all_members(true).await?;
         let subsquare_referendum =
             require_subsquare_referendum(&self.subsquare_client, &chain, db_referendum.index)
                 .await?;
@@ -36,38 +35,9 @@ impl TelegramBot {
         )
         .await?;
 
-        let policy = Policy::policy_for_track(&db_referendum.track);
-        let vote_counts = get_vote_counts(voting_member_count, &opensquare_votes);
-        let block_number = subsquare_referendum.state.block.number;
-        let maybe_blocks_left = match subsquare_referendum.state.status {
-            ReferendumStatus::Deciding => {
-                if let Some(decision_info) = &subsquare_referendum.onchain_data.info.decision_info {
-                    if let Some(decision_start_block) = decision_info.decision_start_block_number {
-                        let decision_end_block = decision_start_block
-                            + subsquare_referendum.track_info.decision_period as u64;
-                        Some(decision_end_block.saturating_sub(block_number))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            ReferendumStatus::Confirming => {
-                if let Some(decision_info) = &subsquare_referendum.onchain_data.info.decision_info {
-                    if let Some(confirm_start_block) = decision_info.confirm_start_block_number {
-                        let confirm_end_block = confirm_start_block
-                            + subsquare_referendum.track_info.confirm_period as u64;
-                        Some(confirm_end_block.saturating_sub(block_number))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        };
+        let policy = self.voting_policy.policy_for_track(&db_referendum.track);
+        let vote_counts = get_vote_counts(&members, &opensquare_votes);
+        let maybe_blocks_left = get_blocks_left(&subsquare_referendum);
 
         let mut message = format!("{}", subsquare_referendum.state.status);
         if let Some(blocks_left) = maybe_blocks_left {
@@ -102,14 +72,28 @@ impl TelegramBot {
             )
         }
 
-        let (_, description_lines) = policy.evaluate(&vote_counts);
+        let participation_comparison = policy
+            .effective_participation_requirement(maybe_blocks_left)
+            .comparison();
+        message = format!(
+            "{message}\nEffective participation threshold: {}{:.1}%",
+            participation_comparison.symbol(),
+            participation_comparison.threshold_rate(),
+        );
+        let (_, description_lines) = policy.evaluate(&vote_counts, maybe_blocks_left);
         message = format!("{message}\n{}", description_lines.join("\n"));
 
         if opensquare_referendum.status.to_lowercase() != "active" {
             message = format!("{message}\n\nMirror referendum is terminated.");
         }
         self.telegram_client
-            .send_message(chat_id, Some(thread_id), &message, true)
+            .send_message_with_reply_markup(
+                chat_id,
+                Some(thread_id),
+                &message,
+                true,
+                Some(get_referendum_keyboard()),
+            )
             .await?;
         Ok(())
     }
@@ -1,6 +1,5 @@
 use crate::command::util::{
-    require_db_referendum, require_opensquare_referendum, require_opensquare_referendum_active,
-    require_thread, require_voting_admin,
+    require_opensquare_referendum, require_opensquare_referendum_active, require_thread,
 };
 use crate::TelegramBot;
 use pdao_types::substrate::chain::Chain;
@@ -10,13 +9,14 @@ impl TelegramBot {
         &self,
         chat_id: i64,
         thread_id: Option<i32>,
-        username: &str,
         topic_status: &str,
         topic_emoji: &str,
     ) -> anyhow::Result<()> {
-        require_voting_admin(username)?;
         let thread_id = require_thread(thread_id)?;
-        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        let Some((_lock, db_referendum)) = self.lock_db_referendum(chat_id, thread_id).await?
+        else {
+            return Ok(());
+        };
         let chain = Chain::from_id(db_referendum.network_id);
         let opensquare_referendum =
             require_opensquare_referendum(&self.opensquare_client, &db_referendum.opensquare_cid)
@@ -1,16 +1,19 @@
-use crate::CONFIG;
+use crate::{TelegramBot, CONFIG};
+use frankenstein::types::User;
 use pdao_opensquare_client::OpenSquareClient;
+use pdao_persistence::postgres::lock::ReferendumLock;
 use pdao_persistence::postgres::PostgreSQLStorage;
 use pdao_subsquare_client::SubSquareClient;
 use pdao_types::governance::opensquare::{
     OpenSquareReferendum, OpenSquareReferendumVote, OpenSquareVote,
 };
-use pdao_types::governance::policy::VoteCounts;
+use pdao_types::governance::policy::{MembershipVoteCounts, VoteCounts};
 use pdao_types::governance::subsquare::SubSquareReferendum;
-use pdao_types::governance::{Referendum, ReferendumStatus};
+use pdao_types::governance::{AccountVote, ConvictionVote, Referendum, ReferendumStatus};
 use pdao_types::substrate::account_id::AccountId;
 use pdao_types::substrate::chain::Chain;
-use pdao_types::Member;
+use pdao_types::{Member, MembershipType, Role};
+use pdao_voter::DryRun;
 
 pub(super) fn require_thread(thread_id: Option<i32>) -> anyhow::Result<i32> {
     if let Some(thread_id) = thread_id {
@@ -126,32 +129,151 @@ pub(super) fn require_subsquare_referendum_active(
     }
 }
 
-pub fn get_vote_counts(member_count: u32, votes: &[OpenSquareReferendumVote]) -> VoteCounts {
-    let mut aye_count = 0;
-    let mut nay_count = 0;
-    let mut abstain_count = 0;
+/// One line per voting account, e.g. `• 1a2b...: AYE 100.0000 DOT`.
+pub(super) fn format_account_votes(chain: &Chain, account_votes: &[AccountVote]) -> String {
+    account_votes
+        .iter()
+        .map(|account_vote| {
+            let delegated = if account_vote.delegated_balance > 0 {
+                format!(
+                    " + {} delegated",
+                    chain.format_balance(account_vote.delegated_balance)
+                )
+            } else {
+                String::new()
+            };
+            format!(
+                "• {}: {} {}{delegated}",
+                account_vote.address,
+                account_vote.vote,
+                chain.format_balance(account_vote.balance),
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub(super) fn format_conviction_vote(
+    chain: &Chain,
+    maybe_conviction_vote: &Option<ConvictionVote>,
+) -> String {
+    match maybe_conviction_vote {
+        Some(ConvictionVote::Standard {
+            aye,
+            conviction,
+            balance,
+        }) => format!(
+            "{} {} with {conviction}x conviction",
+            if *aye { "AYE" } else { "NAY" },
+            chain.format_balance(*balance),
+        ),
+        Some(ConvictionVote::Split { aye, nay }) => format!(
+            "SPLIT {} AYE / {} NAY",
+            chain.format_balance(*aye),
+            chain.format_balance(*nay),
+        ),
+        Some(ConvictionVote::SplitAbstain { aye, nay, abstain }) => format!(
+            "SPLIT {} AYE / {} NAY / {} ABSTAIN",
+            chain.format_balance(*aye),
+            chain.format_balance(*nay),
+            chain.format_balance(*abstain),
+        ),
+        None => "no vote".to_string(),
+    }
+}
+
+/// Formats a simulated batch for Telegram. The summary goes into a code block, since call
+/// names like `batch_all` would otherwise break the Markdown.
+pub(super) fn format_dry_run(chain: &Chain, dry_run: &DryRun) -> String {
+    format!(
+        "```\n{}\n```\nEstimated fee: {}\nCall data: `{}`",
+        dry_run.summary.join("\n"),
+        chain.format_balance(dry_run.estimated_fee),
+        dry_run.call_hex,
+    )
+}
+
+/// Blocks left until the end of the decision period, or the confirmation period when confirming.
+pub fn get_blocks_left(subsquare_referendum: &SubSquareReferendum) -> Option<u64> {
+    let block_number = subsquare_referendum.state.block.number;
+    match subsquare_referendum.state.status {
+        ReferendumStatus::Deciding => {
+            if let Some(decision_info) = &subsquare_referendum.onchain_data.info.decision_info {
+                if let Some(decision_start_block) = decision_info.decision_start_block_number {
+                    let decision_end_block = decision_start_block
+                        + subsquare_referendum.track_info.decision_period as u64;
+                    Some(decision_end_block.saturating_sub(block_number))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        }
+        ReferendumStatus::Confirming => {
+            if let Some(decision_info) = &subsquare_referendum.onchain_data.info.decision_info {
+                if let Some(confirm_start_block) = decision_info.confirm_start_block_number {
+                    let confirm_end_block =
+                        confirm_start_block + subsquare_referendum.track_info.confirm_period as u64;
+                    Some(confirm_end_block.saturating_sub(block_number))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Counts votes per membership type. `members` should include the members on leave, whose
+/// votes still count even though they are not counted as available members.
+pub fn get_vote_counts(members: &[Member], votes: &[OpenSquareReferendumVote]) -> VoteCounts {
+    let mut core = MembershipVoteCounts::default();
+    let mut community = MembershipVoteCounts::default();
+    for member in members.iter().filter(|member| !member.is_on_leave) {
+        match member.membership_type {
+            MembershipType::Core => core.members += 1,
+            MembershipType::Community => community.members += 1,
+        }
+    }
     for vote in votes.iter() {
+        let counts = match members
+            .iter()
+            .find(|member| member.polkadot_address == vote.voter)
+        {
+            Some(member) if member.membership_type == MembershipType::Core => &mut core,
+            _ => &mut community,
+        };
         if vote.choices.contains(&OpenSquareVote::Aye) {
-            aye_count += 1;
+            counts.ayes += 1;
         } else if vote.choices.contains(&OpenSquareVote::Nay) {
-            nay_count += 1;
+            counts.nays += 1;
         } else if vote.choices.contains(&OpenSquareVote::Abstain) {
-            abstain_count += 1;
+            counts.abstains += 1;
         }
     }
-    VoteCounts::new(member_count, aye_count, nay_count, abstain_count)
+    VoteCounts::from_membership_counts(core, community)
 }
 
-pub(super) fn require_voting_admin(username: &str) -> anyhow::Result<()> {
-    if !CONFIG.voter.voting_admin_usernames.contains(username) {
-        Err(anyhow::Error::msg(
-            "This command can only be called by a voting admin.",
-        ))
-    } else {
-        Ok(())
+/// Name of a Telegram user for messages and logs: the username, else the first name, else the
+/// user id.
+pub(crate) fn get_user_display_name(user: &User) -> String {
+    match &user.username {
+        Some(username) => username.clone(),
+        None if !user.first_name.is_empty() => user.first_name.clone(),
+        None => user.id.to_string(),
     }
 }
 
+/// Members are registered by their Telegram username.
+pub(crate) fn require_username(user: &User) -> anyhow::Result<&str> {
+    user.username.as_deref().ok_or(anyhow::anyhow!(
+        "This command needs a Telegram username, set one in the Telegram settings."
+    ))
+}
+
 pub(super) async fn require_member(
     postgres: &PostgreSQLStorage,
     username: &str,
@@ -164,3 +286,59 @@ pub(super) async fn require_member(
         )))
     }
 }
+
+impl TelegramBot {
+    pub(crate) async fn require_role(&self, user_id: i64, role: Role) -> anyhow::Result<()> {
+        if CONFIG.telegram.get_admin_user_ids().contains(&user_id) {
+            return Ok(());
+        }
+        let user_roles = self.postgres.get_user_roles(user_id).await?;
+        if user_roles.iter().any(|user_role| *user_role >= role) {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "This command requires the {role} role. Your Telegram user id is {user_id}."
+            ))
+        }
+    }
+
+    /// Takes the lock of the topic's referendum for a state-changing action, and reads the
+    /// referendum under it. Replies in the topic when another action holds the lock.
+    pub(super) async fn lock_db_referendum(
+        &self,
+        chat_id: i64,
+        thread_id: i32,
+    ) -> anyhow::Result<Option<(ReferendumLock, Referendum)>> {
+        let maybe_lock = self.try_lock_db_referendum(chat_id, thread_id).await?;
+        if maybe_lock.is_none() {
+            self.telegram_client
+                .send_message(
+                    chat_id,
+                    Some(thread_id),
+                    "⏳ Another action is in progress on this referendum. Try again once it's done.",
+                    true,
+                )
+                .await?;
+        }
+        Ok(maybe_lock)
+    }
+
+    /// Same as `lock_db_referendum`, without the reply, for automated actions.
+    pub(super) async fn try_lock_db_referendum(
+        &self,
+        chat_id: i64,
+        thread_id: i32,
+    ) -> anyhow::Result<Option<(ReferendumLock, Referendum)>> {
+        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        let Some(lock) = self.postgres.try_lock_referendum(db_referendum.id).await? else {
+            log::warn!(
+                "Referendum {} is locked by another action.",
+                db_referendum.index
+            );
+            return Ok(None);
+        };
+        // the other action may have changed the referendum while holding the lock
+        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        Ok(Some((lock, db_referendum)))
+    }
+}
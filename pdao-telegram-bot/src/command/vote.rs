@@ -1,27 +1,269 @@
 use crate::command::util::{
-    get_vote_counts, require_db_referendum, require_db_referendum_is_active,
+    format_account_votes, get_blocks_left, get_vote_counts, require_db_referendum_is_active,
     require_opensquare_referendum, require_opensquare_votes, require_subsquare_referendum,
-    require_subsquare_referendum_active, require_thread, require_voting_admin,
+    require_subsquare_referendum_active, require_thread,
 };
 use crate::TelegramBot;
-use pdao_types::governance::policy::Policy;
+use pdao_types::governance::opensquare::{OpenSquareReferendum, OpenSquareReferendumVote};
+use pdao_types::governance::subsquare::SubSquareReferendum;
+use pdao_types::governance::{
+    AccountVote, ConvictionVote, Referendum, VoteIntent, VoteIntentMemberVote, VoteIntentRecord,
+    VoteIntentStatus, VotePolicyRecord,
+};
+use pdao_types::outbox::OutboxEffect;
 use pdao_types::substrate::chain::Chain;
+use pdao_voter::ChainVotes;
 
 impl TelegramBot {
+    /// Saves the vote intent, then submits the vote and marks the intent as submitted, so that
+    /// the vote can still be recorded after a crash.
+    pub(crate) async fn submit_vote_intent(
+        &self,
+        chain: &Chain,
+        db_referendum: &Referendum,
+        vote_intent_record: &VoteIntentRecord,
+        account_votes: &[AccountVote],
+        conviction: u8,
+    ) -> anyhow::Result<(u32, u64, u32)> {
+        let vote_intent_id = self
+            .postgres
+            .save_vote_intent(
+                db_referendum.network_id,
+                db_referendum.id,
+                db_referendum.index,
+                vote_intent_record,
+            )
+            .await? as u32;
+        log::info!("Submit vote.");
+        let (block_hash, block_number, extrinsic_index) = match self
+            .voter
+            .vote(chain, db_referendum.index, account_votes, conviction)
+            .await
+        {
+            Ok(result) => result,
+            Err(error) => {
+                self.postgres
+                    .set_vote_intent_unsubmitted(
+                        vote_intent_id,
+                        VoteIntentStatus::Failed,
+                        &error.to_string(),
+                    )
+                    .await?;
+                return Err(error);
+            }
+        };
+        self.postgres
+            .set_vote_intent_submitted(vote_intent_id, &block_hash, block_number, extrinsic_index)
+            .await?;
+        Ok((vote_intent_id, block_number, extrinsic_index))
+    }
+
+    /// Whether the votes of a pending intent are on chain, i.e. the submission went through but
+    /// a restart interrupted it before the intent was updated.
+    async fn find_vote_intent_on_chain(
+        &self,
+        chain: &Chain,
+        db_referendum: &Referendum,
+        vote_intent: &VoteIntent,
+    ) -> anyhow::Result<Option<ChainVotes>> {
+        let account_votes = &vote_intent.record.account_votes;
+        let account_addresses: Vec<String> = account_votes
+            .iter()
+            .map(|account_vote| account_vote.address.clone())
+            .collect();
+        let chain_votes = self
+            .voter
+            .get_chain_votes(
+                chain,
+                db_referendum.track.id(),
+                db_referendum.index,
+                &account_addresses,
+            )
+            .await?;
+        let is_on_chain = account_votes.iter().all(|account_vote| {
+            let expected_vote = ConvictionVote::new(
+                account_vote.vote,
+                account_vote.balance,
+                vote_intent.record.conviction,
+            );
+            chain_votes
+                .account_votes
+                .iter()
+                .any(|(address, chain_vote)| {
+                    *address == account_vote.address
+                        && chain_vote
+                            .map(|chain_vote| expected_vote.matches(&chain_vote))
+                            .unwrap_or(false)
+                })
+        });
+        Ok(if is_on_chain { Some(chain_votes) } else { None })
+    }
+
+    /// Records the votes submitted before a restart and enqueues their posts. A submission
+    /// interrupted before the intent got updated is recorded when its votes are on chain, and
+    /// abandoned otherwise.
+    pub(crate) async fn recover_vote_intents(&self) -> anyhow::Result<()> {
+        for vote_intent in self.postgres.get_unrecorded_vote_intents().await? {
+            let Some(db_referendum) = self
+                .postgres
+                .get_referendum_by_id(vote_intent.referendum_id)
+                .await?
+            else {
+                log::error!(
+                    "Referendum {} of vote intent {} not found.",
+                    vote_intent.referendum_id,
+                    vote_intent.id
+                );
+                continue;
+            };
+            let chain = Chain::from_id(vote_intent.network_id);
+            let (message, maybe_recorded_vote) = match (
+                vote_intent.status,
+                vote_intent.block_number,
+            ) {
+                (VoteIntentStatus::Submitted, Some(block_number)) => {
+                    log::info!("Record the submitted vote of intent {}.", vote_intent.id);
+                    let vote_id = self
+                        .postgres
+                        .record_vote(vote_intent.id, None, None)
+                        .await? as u32;
+                    let link = format!(
+                        "https://assethub-{}.subscan.io/extrinsic/{}-{}",
+                        chain.chain.to_lowercase(),
+                        block_number,
+                        vote_intent.extrinsic_index.unwrap_or_default(),
+                    );
+                    (
+                        format!("♻️ Recorded the vote submitted before the restart.\n{link}"),
+                        Some((vote_id, link)),
+                    )
+                }
+                _ => {
+                    log::warn!(
+                        "Vote intent {} was interrupted during submission.",
+                        vote_intent.id
+                    );
+                    match self
+                        .find_vote_intent_on_chain(&chain, &db_referendum, &vote_intent)
+                        .await
+                    {
+                        Ok(Some(chain_votes)) => {
+                            log::info!(
+                                "Vote intent {} is on chain, record the vote.",
+                                vote_intent.id
+                            );
+                            self.postgres
+                                .set_vote_intent_submitted(
+                                    vote_intent.id,
+                                    &chain_votes.block_hash,
+                                    chain_votes.block_number,
+                                    0,
+                                )
+                                .await?;
+                            let vote_id = self
+                                .postgres
+                                .record_vote(vote_intent.id, None, None)
+                                .await? as u32;
+                            let link = format!(
+                                "https://assethub-{}.subscan.io/block/{}",
+                                chain.chain.to_lowercase(),
+                                chain_votes.block_number,
+                            );
+                            (
+                                format!(
+                                    "♻️ Recorded the vote interrupted by the restart, its votes are on chain at block {}.\n{link}",
+                                    chain_votes.block_number,
+                                ),
+                                Some((vote_id, link)),
+                            )
+                        }
+                        Ok(None) => {
+                            self.postgres
+                                .set_vote_intent_unsubmitted(
+                                    vote_intent.id,
+                                    VoteIntentStatus::Abandoned,
+                                    "Interrupted during submission, not on chain.",
+                                )
+                                .await?;
+                            (
+                                "⚠️ A vote submission was interrupted by a restart and is not on chain. The vote is discarded.".to_string(),
+                                None,
+                            )
+                        }
+                        Err(error) => {
+                            log::error!(
+                                "Cannot read the on-chain votes of vote intent {}: {error}",
+                                vote_intent.id
+                            );
+                            self.postgres
+                                .set_vote_intent_unsubmitted(
+                                    vote_intent.id,
+                                    VoteIntentStatus::Abandoned,
+                                    "Interrupted during submission.",
+                                )
+                                .await?;
+                            (
+                                "⚠️ A vote submission was interrupted by a restart, it may or may not be on chain. Run /reconcile to compare the recorded vote with the chain.".to_string(),
+                                None,
+                            )
+                        }
+                    }
+                }
+            };
+            self.telegram_client
+                .send_message(
+                    db_referendum.telegram_chat_id,
+                    Some(db_referendum.telegram_topic_id),
+                    &message,
+                    true,
+                )
+                .await?;
+            if let Some((vote_id, link)) = maybe_recorded_vote {
+                if let Err(error) = self
+                    .enqueue_recovered_vote_posts(
+                        &chain,
+                        &db_referendum,
+                        vote_id,
+                        &vote_intent.record,
+                        &link,
+                    )
+                    .await
+                {
+                    log::error!("Cannot enqueue the posts of recovered vote {vote_id}: {error}");
+                }
+            }
+        }
+        self.deliver_outbox_items().await
+    }
+
+    /// Automated votes are skipped when another action holds the lock, or has already voted
+    /// the current outcome.
     #[allow(clippy::cognitive_complexity)]
     pub(crate) async fn process_vote_command(
         &self,
         chat_id: i64,
         thread_id: Option<i32>,
-        username: &str,
         post_feedback: bool,
+        is_automated: bool,
     ) -> anyhow::Result<()> {
-        require_voting_admin(username)?;
+        if self.voter.is_safe_mode() {
+            return self.process_simulate_vote_command(chat_id, thread_id).await;
+        }
         let thread_id = require_thread(thread_id)?;
-        let db_referendum = require_db_referendum(&self.postgres, chat_id, thread_id).await?;
+        let maybe_lock = if is_automated {
+            self.try_lock_db_referendum(chat_id, thread_id).await?
+        } else {
+            self.lock_db_referendum(chat_id, thread_id).await?
+        };
+        let Some((_lock, db_referendum)) = maybe_lock else {
+            if is_automated {
+                log::info!("Skip automated vote on locked topic {thread_id}.");
+            }
+            return Ok(());
+        };
         require_db_referendum_is_active(&db_referendum)?;
         let chain = Chain::from_id(db_referendum.network_id);
-        let voting_member_count = self.postgres.get_all_members(false).await?.len() as u32;
+        let members = self.postgres.get_all_members(true).await?;
         let subsquare_referendum =
             require_subsquare_referendum(&self.subsquare_client, &chain, db_referendum.index)
                 .await?;
@@ -39,10 +281,29 @@ impl TelegramBot {
             &member_account_ids,
         )
         .await?;
-        let policy = Policy::policy_for_track(&db_referendum.track);
-        let vote_counts = get_vote_counts(voting_member_count, &opensquare_votes);
+        let policy = self.voting_policy.policy_for_track(&db_referendum.track);
+        let vote_counts = get_vote_counts(&members, &opensquare_votes);
         let past_votes = self.postgres.get_referendum_votes(db_referendum.id).await?;
-        let (evaluation, description_lines) = policy.evaluate(&vote_counts);
+        let blocks_left = get_blocks_left(&subsquare_referendum);
+        let (evaluation, description_lines) = policy.evaluate(&vote_counts, blocks_left);
+        if is_automated {
+            // another action may have voted since the auto-voter read the last vote
+            if let Some(last_vote) = self
+                .postgres
+                .get_referendum_last_vote(db_referendum.id)
+                .await?
+            {
+                if last_vote.is_forced || last_vote.on_chain_vote() == evaluation.on_chain_vote()? {
+                    log::info!(
+                        "Skip automated vote, {} referendum {} last vote is already {}.",
+                        chain.chain,
+                        db_referendum.index,
+                        last_vote.on_chain_vote(),
+                    );
+                    return Ok(());
+                }
+            }
+        }
 
         self.telegram_client
             .send_message(
@@ -58,152 +319,250 @@ impl TelegramBot {
             chain.chain,
             db_referendum.index
         );
-        let balance = 10u128.pow(chain.token_decimals as u32);
-        let conviction = 1;
-        log::info!("Submit vote.");
-        let (block_hash, block_number, extrinsic_index) = self
+        let account_votes = self
             .voter
-            .vote(
+            .get_account_votes(
                 &chain,
-                db_referendum.index,
+                db_referendum.track.id(),
                 db_referendum.has_coi,
-                evaluation.simplify()?,
-                balance,
+                evaluation.on_chain_vote()?,
+                &policy.vote_balance(),
+            )
+            .await?;
+        let balance = account_votes
+            .iter()
+            .map(|account_vote| account_vote.balance)
+            .sum();
+        let conviction = policy.conviction();
+        let vote_intent_record = VoteIntentRecord {
+            vote: evaluation.simplify()?,
+            split: evaluation.split(),
+            balance,
+            conviction,
+            has_coi: db_referendum.has_coi,
+            is_forced: false,
+            post_feedback,
+            policy_record: Some(VotePolicyRecord {
+                policy_version: self.voting_policy.version().to_string(),
+                policy,
+                vote_counts,
+                blocks_left,
+                description_lines,
+            }),
+            account_votes: account_votes.clone(),
+            member_votes: opensquare_votes
+                .iter()
+                .map(|member_vote| VoteIntentMemberVote {
+                    cid: member_vote.cid.clone(),
+                    address: member_vote.address.to_ss58_check(),
+                    vote: member_vote.get_vote(),
+                    feedback: member_vote.remark.clone(),
+                })
+                .collect(),
+        };
+        let (vote_intent_id, block_number, extrinsic_index) = self
+            .submit_vote_intent(
+                &chain,
+                &db_referendum,
+                &vote_intent_record,
+                &account_votes,
                 conviction,
             )
             .await?;
         log::info!("Vote submitted.");
-        let (subsquare_cid, subsquare_index) = if post_feedback {
+        log::info!("Save vote in DB.");
+        let vote_id = self
+            .postgres
+            .record_vote(vote_intent_id, None, None)
+            .await? as u32;
+        let link = format!(
+            "https://assethub-{}.subscan.io/extrinsic/{}-{}",
+            chain.chain.to_lowercase(),
+            block_number,
+            extrinsic_index,
+        );
+        self.enqueue_vote_posts(
+            &chain,
+            &db_referendum,
+            &subsquare_referendum,
+            &opensquare_referendum,
+            &opensquare_votes,
+            vote_id,
+            &vote_intent_record,
+            &link,
+        )
+        .await?;
+        self.deliver_outbox_items().await
+    }
+
+    /// Leaves the posts of a recorded vote to the outbox, which retries them: the SubSquare
+    /// comment with the members' feedback summary when asked for, the topic name and message,
+    /// and the OpenSquare appendant.
+    pub(crate) async fn enqueue_vote_posts(
+        &self,
+        chain: &Chain,
+        db_referendum: &Referendum,
+        subsquare_referendum: &SubSquareReferendum,
+        opensquare_referendum: &OpenSquareReferendum,
+        opensquare_votes: &[OpenSquareReferendumVote],
+        vote_id: u32,
+        record: &VoteIntentRecord,
+        link: &str,
+    ) -> anyhow::Result<()> {
+        let Some(policy_record) = record.policy_record.as_ref() else {
+            anyhow::bail!("Vote {vote_id} has no policy record.");
+        };
+        let (evaluation, _) = policy_record.reevaluate();
+        let description_lines = &policy_record.description_lines;
+        let chat_id = db_referendum.telegram_chat_id;
+        let thread_id = db_referendum.telegram_topic_id;
+        let votes = self.postgres.get_referendum_votes(db_referendum.id).await?;
+        let previous_vote_count = votes.iter().filter(|vote| vote.id < vote_id).count();
+        let maybe_feedback = if record.post_feedback {
             log::info!("Get OpenAI feedback summary.");
-            let feedback = self
+            match self
                 .openai_client
-                .fetch_feedback_summary(
-                    &chain,
-                    &subsquare_referendum,
-                    &evaluation,
-                    &opensquare_votes,
-                )
-                .await?;
-            log::info!("Post SubSquare comment.");
-            let (cid, index) = if let Some(Some(first_vote_cid)) = past_votes
-                .first()
-                .map(|first_vote| first_vote.subsquare_comment_cid.clone())
+                .fetch_feedback_summary(chain, subsquare_referendum, &evaluation, opensquare_votes)
+                .await
             {
-                let response = self
-                    .subsquare_client
-                    .post_comment_reply(
-                        &chain,
-                        &subsquare_referendum,
-                        &db_referendum.opensquare_cid,
-                        &first_vote_cid,
-                        past_votes.len() as u32,
-                        &evaluation,
-                        &description_lines,
-                        db_referendum.has_coi,
-                        &feedback,
-                    )
-                    .await?;
-                (response.cid, response.index)
-            } else {
-                let response = self
-                    .subsquare_client
-                    .post_comment(
-                        &chain,
-                        &subsquare_referendum,
-                        &db_referendum.opensquare_cid,
-                        past_votes.len() as u32,
-                        &evaluation,
-                        &description_lines,
-                        db_referendum.has_coi,
-                        &feedback,
-                    )
-                    .await?;
-                (response.cid, response.index)
-            };
-            (Some(cid), Some(index))
+                Ok(feedback) => Some(feedback),
+                Err(error) => {
+                    log::error!("Cannot get OpenAI feedback summary: {error}");
+                    None
+                }
+            }
         } else {
             log::info!("Skip SubSquare comment.");
-            (None, None)
+            None
         };
-        log::info!("Save vote in DB.");
-        let vote_id = self
-            .postgres
-            .save_vote(
-                db_referendum.network_id,
-                db_referendum.id,
-                db_referendum.index,
-                &block_hash,
-                block_number,
-                extrinsic_index,
-                evaluation.simplify()?,
-                balance,
-                conviction,
-                subsquare_cid.as_deref(),
-                subsquare_index,
-                db_referendum.has_coi,
-                false,
-            )
-            .await?;
-        for member_vote in opensquare_votes.iter() {
+        if let Some(feedback) = maybe_feedback.as_ref() {
+            let content = self.subsquare_client.get_vote_comment_content(
+                chain,
+                &db_referendum.opensquare_cid,
+                previous_vote_count as u32,
+                &evaluation,
+                description_lines,
+                record.has_coi,
+                feedback,
+            )?;
             self.postgres
-                .save_member_vote(
-                    vote_id as u32,
-                    &member_vote.cid,
-                    db_referendum.network_id,
-                    db_referendum.id,
-                    db_referendum.index,
-                    &member_vote.address.to_ss58_check(),
-                    member_vote.get_vote(),
-                    &member_vote.remark,
+                .enqueue_outbox_item(
+                    &format!("vote-{vote_id}-subsquare-comment"),
+                    &OutboxEffect::SubSquareComment {
+                        network_id: db_referendum.network_id,
+                        referendum_index: db_referendum.index,
+                        proposed_height: subsquare_referendum.extrinsic.block_number,
+                        reply_to_comment_cid: votes
+                            .first()
+                            .and_then(|first_vote| first_vote.subsquare_comment_cid.clone()),
+                        content,
+                        vote_id,
+                        chat_id,
+                        thread_id,
+                    },
                 )
                 .await?;
         }
-        self.postgres
-            .delete_referendum_pending_member_votes(db_referendum.id)
-            .await?;
-        self.postgres
-            .set_referendum_last_vote_id(db_referendum.id, Some(vote_id as u32))
-            .await?;
-        let coi_message = if db_referendum.has_coi {
-            "CoI reported. DV delegation voted abstain."
+        let coi_message = if record.has_coi {
+            "CoI reported."
         } else {
-            "No CoI reported. DV delegation exercised."
+            "No CoI reported."
         };
-        let mut message = format!(
-            "Vote #{} submitted.\n{}\n{coi_message}\nhttps://assethub-{}.subscan.io/extrinsic/{}-{}",
-            past_votes.len() + 1,
+        let feedback_message = match (record.post_feedback, maybe_feedback.is_some()) {
+            (false, _) => "Feedback skipped.",
+            (true, true) => "Feedback is being posted on SubSquare.",
+            (true, false) => "⚠️ Feedback summary failed, SubSquare comment skipped.",
+        };
+        let message = format!(
+            "Vote #{} submitted.\n{}\n{coi_message}\n{}\n{link}\n{feedback_message}",
+            previous_vote_count + 1,
             description_lines.join("\n"),
-            chain.chain.to_lowercase(),
-            block_number,
-            extrinsic_index,
+            format_account_votes(chain, &record.account_votes),
         );
-        if let Some(subsquare_index) = subsquare_index {
-            message = format!(
-                "{message}\nhttps://{}.subsquare.io/referenda/{}#{subsquare_index}",
-                chain.chain.to_lowercase(),
-                db_referendum.index,
-            );
-        } else {
-            message = format!("{message}\nFeedback skipped.",);
-        }
-
-        self.telegram_client
-            .update_referendum_topic_name(
-                chat_id,
-                thread_id,
-                &opensquare_referendum.title,
-                db_referendum.has_coi,
-                None,
-                &format!("V{}", past_votes.len() + 1),
-                db_referendum.status.get_status_icon(),
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{vote_id}-topic-name"),
+                &OutboxEffect::TelegramTopicName {
+                    chat_id,
+                    thread_id,
+                    name: opensquare_referendum.title.clone(),
+                    has_coi: record.has_coi,
+                    vote_count_status: format!("V{}", previous_vote_count + 1),
+                    status_emoji: db_referendum.status.get_status_icon().to_string(),
+                },
             )
             .await?;
-        self.telegram_client
-            .send_message(chat_id, Some(thread_id), &message, true)
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{vote_id}-message"),
+                &OutboxEffect::TelegramMessage {
+                    chat_id,
+                    thread_id: Some(thread_id),
+                    message: message.clone(),
+                },
+            )
             .await?;
-        self.opensquare_client
-            .make_appendant_on_proposal(&chain, &db_referendum.opensquare_cid, &message)
+        self.postgres
+            .enqueue_outbox_item(
+                &format!("vote-{vote_id}-opensquare-appendant"),
+                &OutboxEffect::OpenSquareAppendant {
+                    network_id: db_referendum.network_id,
+                    cid: db_referendum.opensquare_cid.clone(),
+                    content: message,
+                },
+            )
             .await?;
         Ok(())
     }
+
+    /// Enqueues the posts of a vote recorded by the recovery, reading the referendum from
+    /// SubSquare and OpenSquare again.
+    async fn enqueue_recovered_vote_posts(
+        &self,
+        chain: &Chain,
+        db_referendum: &Referendum,
+        vote_id: u32,
+        record: &VoteIntentRecord,
+        link: &str,
+    ) -> anyhow::Result<()> {
+        let opensquare_referendum =
+            require_opensquare_referendum(&self.opensquare_client, &db_referendum.opensquare_cid)
+                .await?;
+        if record.is_forced {
+            return self
+                .enqueue_force_vote_posts(
+                    chain,
+                    db_referendum,
+                    &opensquare_referendum,
+                    vote_id,
+                    record,
+                    link,
+                )
+                .await;
+        }
+        let subsquare_referendum =
+            require_subsquare_referendum(&self.subsquare_client, chain, db_referendum.index)
+                .await?;
+        let member_account_ids = self
+            .postgres
+            .get_all_member_account_ids_for_chain(true, Chain::polkadot().id)
+            .await?;
+        let opensquare_votes = require_opensquare_votes(
+            &self.opensquare_client,
+            &db_referendum.opensquare_cid,
+            &member_account_ids,
+        )
+        .await?;
+        self.enqueue_vote_posts(
+            chain,
+            db_referendum,
+            &subsquare_referendum,
+            &opensquare_referendum,
+            &opensquare_votes,
+            vote_id,
+            record,
+            link,
+        )
+        .await
+    }
 }
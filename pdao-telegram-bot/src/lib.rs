@@ -1,28 +1,42 @@
 use async_trait::async_trait;
-use frankenstein::types::Message;
+use frankenstein::types::{Message, User};
 use frankenstein::updates::{Update, UpdateContent};
 use lazy_static::lazy_static;
 use pdao_config::Config;
 use pdao_service::Service;
 
-use crate::command::util::{get_vote_counts, require_subsquare_referendum};
+use crate::command::util::{
+    get_blocks_left, get_user_display_name, get_vote_counts, require_subsquare_referendum,
+    require_username,
+};
+use crate::command::{get_command_role, may_wait_for_signature};
+use pdao_keystore::Keystore;
 use pdao_openai_client::OpenAIClient;
 use pdao_opensquare_client::OpenSquareClient;
 use pdao_persistence::postgres::PostgreSQLStorage;
 use pdao_referendum_importer::{ReferendumImportError, ReferendumImporter};
 use pdao_subsquare_client::SubSquareClient;
 use pdao_substrate_client::SubstrateClient;
-use pdao_telegram_client::TelegramClient;
-use pdao_types::governance::policy::Policy;
+use pdao_telegram_client::{
+    format_proposal, format_proposal_diff, TelegramClient, CONFIRM_CALLBACK_PREFIX,
+};
+use pdao_types::audit::AUTO_VOTER_ACTOR;
+use pdao_types::governance::policy::{PolicyEvaluation, VotingPolicy};
 use pdao_types::governance::subsquare::SubSquareReferendum;
 use pdao_types::governance::track::Track;
-use pdao_types::governance::{Referendum, ReferendumStatus};
+use pdao_types::governance::{OnChainVote, Referendum, ReferendumStatus};
 use pdao_types::substrate::chain::Chain;
-use pdao_voter::Voter;
+use pdao_types::substrate::referendum::ReferendumLookup;
+use pdao_voter::{SigningRequest, Voter};
 use regex::Regex;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
 
 mod command;
 mod metrics;
+mod outbox;
 
 lazy_static! {
     static ref CONFIG: Config = Config::default();
@@ -32,6 +46,32 @@ lazy_static! {
     static ref SPLITTER_REGEX: Regex = Regex::new(r"\s+").unwrap();
 }
 
+/// Commands that may wait for an offline signature at the same time, any more get turned down.
+const MAX_SIGNING_COMMANDS: usize = 4;
+
+/// The command of a text message or a pressed button, without the bot username.
+fn get_update_command(update: &Update) -> Option<String> {
+    match &update.content {
+        UpdateContent::Message(message) => {
+            let text = message.text.as_deref()?;
+            if !CMD_REGEX.is_match(text) {
+                return None;
+            }
+            let command = SPLITTER_REGEX.split(text).next()?;
+            Some(command.replace(&CONFIG.telegram.bot_username, ""))
+        }
+        UpdateContent::CallbackQuery(callback_query) => {
+            let data = callback_query.data.as_deref()?;
+            Some(
+                data.strip_prefix(CONFIRM_CALLBACK_PREFIX)
+                    .unwrap_or(data)
+                    .to_string(),
+            )
+        }
+        _ => None,
+    }
+}
+
 fn get_vote_name<'a>(vote: Option<bool>) -> &'a str {
     if let Some(vote) = vote {
         if vote {
@@ -58,18 +98,36 @@ pub struct TelegramBot {
     openai_client: OpenAIClient,
     referendum_importer: ReferendumImporter,
     voter: Voter,
+    voting_policy: VotingPolicy,
+    /// Networks with a voter configuration, which the periodic tasks run for.
+    chains: Vec<Chain>,
+    /// Last outcome simulated by the auto-voter per referendum id in safe mode, where no vote
+    /// gets recorded.
+    simulated_votes: Mutex<BTreeMap<u32, OnChainVote>>,
 }
 
 impl TelegramBot {
     pub async fn new() -> anyhow::Result<Self> {
+        let keystore = Arc::new(Keystore::load(&CONFIG)?);
         Ok(Self {
             postgres: PostgreSQLStorage::new(&CONFIG).await?,
-            opensquare_client: OpenSquareClient::new(&CONFIG)?,
-            subsquare_client: SubSquareClient::new(&CONFIG)?,
+            opensquare_client: OpenSquareClient::new(&CONFIG, keystore.clone())?,
+            subsquare_client: SubSquareClient::new(&CONFIG, keystore.clone())?,
             telegram_client: TelegramClient::new(&CONFIG),
             openai_client: OpenAIClient::new(&CONFIG)?,
-            referendum_importer: ReferendumImporter::new(&CONFIG).await?,
-            voter: Voter::new(&CONFIG).await?,
+            referendum_importer: ReferendumImporter::new(&CONFIG, keystore.clone()).await?,
+            voter: Voter::new(&CONFIG, keystore).await?,
+            voting_policy: VotingPolicy::load(
+                &CONFIG.get_voting_policy_file_path(),
+                &CONFIG.voter.voting_policy_version,
+            )?,
+            chains: CONFIG
+                .voter
+                .get_network_configs()
+                .iter()
+                .map(|network_config| Chain::from_str(&network_config.chain))
+                .collect::<Result<_, _>>()?,
+            simulated_votes: Mutex::new(BTreeMap::new()),
         })
     }
 
@@ -77,30 +135,69 @@ impl TelegramBot {
         &self,
         chat_id: i64,
         thread_id: Option<i32>,
-        username: &str,
+        user: &User,
         command: &str,
         args: &[String],
     ) -> anyhow::Result<()> {
         log::info!("Process command {command} for chat {chat_id} thread {thread_id:?} with arguments: {args:?}");
+        let result = self
+            .run_command(chat_id, thread_id, user, command, args)
+            .await;
+        self.save_audit_log_entry(
+            &get_user_display_name(user),
+            Some(user.id as i64),
+            command,
+            args,
+            chat_id,
+            thread_id,
+            &result,
+        )
+        .await;
+        result
+    }
+
+    async fn run_command(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+        user: &User,
+        command: &str,
+        args: &[String],
+    ) -> anyhow::Result<()> {
+        // roles are keyed by the user id, the name is only displayed
+        let user_id = user.id as i64;
+        let display_name = get_user_display_name(user);
+        if let Some(role) = get_command_role(command) {
+            self.require_role(user_id, role).await?;
+        }
         match command {
             "/archive" => {
-                self.process_archive_command(chat_id, thread_id, username)
-                    .await?;
+                self.process_archive_command(chat_id, thread_id).await?;
+            }
+            "/audit" => {
+                self.process_audit_command(chat_id, thread_id).await?;
+            }
+            "/decode" => {
+                self.process_decode_command(chat_id, thread_id).await?;
             }
             "/feedbacksummary" => {
                 self.process_feedback_summary_command(chat_id, thread_id)
                     .await?;
             }
             "/forceabstain" => {
-                self.process_force_vote_command(chat_id, thread_id, username, None)
+                self.process_force_vote_command(chat_id, thread_id, None)
                     .await?;
             }
             "/forceaye" => {
-                self.process_force_vote_command(chat_id, thread_id, username, Some(true))
+                self.process_force_vote_command(chat_id, thread_id, Some(true))
                     .await?;
             }
             "/forcenay" => {
-                self.process_force_vote_command(chat_id, thread_id, username, Some(false))
+                self.process_force_vote_command(chat_id, thread_id, Some(false))
+                    .await?;
+            }
+            "/grant" => {
+                self.process_role_command(chat_id, thread_id, user_id, &display_name, args, true)
                     .await?;
             }
             "/import" => {
@@ -108,47 +205,61 @@ impl TelegramBot {
                 self.process_import_command(chat_id, thread_id, args, polkadot_snapshot_height)
                     .await?;
             }
+            "/reconcile" => {
+                self.process_reconcile_command(chat_id, thread_id).await?;
+            }
+            "/reevaluate" => {
+                self.process_reevaluate_command(chat_id, thread_id).await?;
+            }
             "/removevote" => {
-                self.process_remove_vote_command(chat_id, thread_id, username)
-                    .await?
+                self.process_remove_vote_command(chat_id, thread_id).await?;
+            }
+            "/revoke" => {
+                self.process_role_command(chat_id, thread_id, user_id, &display_name, args, false)
+                    .await?;
+            }
+            "/simulatevote" => {
+                self.process_simulate_vote_command(chat_id, thread_id)
+                    .await?;
+            }
+            "/signature" => {
+                self.process_signature_command(chat_id, thread_id, args)
+                    .await?;
             }
             "/status" => {
                 self.process_status_command(chat_id, thread_id).await?;
             }
             "/terminate" => {
-                self.process_terminate_command(chat_id, thread_id, username, "DONE", "✅")
+                self.process_terminate_command(chat_id, thread_id, "DONE", "✅")
                     .await?;
             }
             "/timeout" => {
-                self.process_terminate_command(chat_id, thread_id, username, "MISSED", "🏁")
+                self.process_terminate_command(chat_id, thread_id, "MISSED", "🏁")
                     .await?;
             }
             "/vote" => {
-                self.process_vote_command(chat_id, thread_id, username, true)
+                self.process_vote_command(chat_id, thread_id, true, false)
                     .await?;
             }
             "/votewithoutfeedback" => {
-                self.process_vote_command(chat_id, thread_id, username, false)
+                self.process_vote_command(chat_id, thread_id, false, false)
                     .await?;
             }
             "/notify" => {
-                self.process_notify_command(chat_id, thread_id, username)
-                    .await?;
+                self.process_notify_command(chat_id, thread_id).await?;
             }
             "/reportcoi" => {
-                self.process_coi_command(chat_id, thread_id, true, username)
-                    .await?;
+                self.process_coi_command(chat_id, thread_id, true).await?;
             }
             "/removecoi" => {
-                self.process_coi_command(chat_id, thread_id, false, username)
-                    .await?;
+                self.process_coi_command(chat_id, thread_id, false).await?;
             }
             "/leave" => {
-                self.process_mark_leave_command(chat_id, thread_id, username)
+                self.process_mark_leave_command(chat_id, thread_id, require_username(user)?)
                     .await?;
             }
             "/return" => {
-                self.process_mark_return_command(chat_id, thread_id, username)
+                self.process_mark_return_command(chat_id, thread_id, require_username(user)?)
                     .await?;
             }
             "/memberlist" => {
@@ -165,7 +276,7 @@ impl TelegramBot {
         &self,
         chat_id: i64,
         thread_id: Option<i32>,
-        username: &str,
+        user: &User,
         text: &str,
     ) -> anyhow::Result<()> {
         if CMD_REGEX.is_match(text) {
@@ -182,7 +293,7 @@ impl TelegramBot {
                 )
             };
             let command = command.replace(&CONFIG.telegram.bot_username, "");
-            self.process_command(chat_id, thread_id, username, &command, &arguments)
+            self.process_command(chat_id, thread_id, user, &command, &arguments)
                 .await?;
         } /* else if thread_id == Some(CONFIG.telegram.bot_chat_thread_id) {
               let response = self.openai_client.fetch_chat_response(username, text).await?;
@@ -198,16 +309,9 @@ impl TelegramBot {
 
     async fn process_message(&self, message: &Message) -> anyhow::Result<()> {
         // text message
-        if let Some(Some(username)) = &message.from.as_ref().map(|a| a.username.as_ref()) {
-            if let Some(text) = &message.text {
-                self.process_text_message(
-                    message.chat.id,
-                    message.message_thread_id,
-                    username,
-                    text,
-                )
+        if let (Some(user), Some(text)) = (&message.from, &message.text) {
+            self.process_text_message(message.chat.id, message.message_thread_id, user, text)
                 .await?;
-            }
         }
         Ok(())
     }
@@ -231,12 +335,49 @@ impl TelegramBot {
                         .await;
                 }
             }
-            UpdateContent::CallbackQuery(_callback_query) => (),
+            UpdateContent::CallbackQuery(callback_query) => {
+                if let Err(error) = self.process_callback_query(callback_query).await {
+                    log::error!(
+                        "Error while processing callback query #{}: {:?}",
+                        callback_query.id,
+                        error,
+                    );
+                    let _ = self
+                        .telegram_client
+                        .answer_callback_query(&callback_query.id, Some(&error.to_string()), true)
+                        .await;
+                }
+            }
             UpdateContent::ChatMember(_chat_member_updated) => (),
             UpdateContent::MyChatMember(_chat_member_updated) => (),
             _ => (),
         }
     }
+
+    /// Turns down a command that would wait for a signature while too many already do.
+    async fn reject_signing_update(&self, update: &Update) {
+        let message = "Too many commands are waiting for a signature, please try again later.";
+        match &update.content {
+            UpdateContent::Message(message_update) => {
+                let _ = self
+                    .telegram_client
+                    .send_message(
+                        CONFIG.telegram.chat_id,
+                        message_update.message_thread_id,
+                        message,
+                        true,
+                    )
+                    .await;
+            }
+            UpdateContent::CallbackQuery(callback_query) => {
+                let _ = self
+                    .telegram_client
+                    .answer_callback_query(&callback_query.id, Some(message), true)
+                    .await;
+            }
+            _ => (),
+        }
+    }
 }
 
 async fn get_polkadot_snapshot_height() -> anyhow::Result<u64> {
@@ -284,6 +425,95 @@ impl TelegramBot {
         Ok(())
     }
 
+    /// Posts a call-level diff when the proposal has been re-noted under a new lookup, so that
+    /// members know that they are no longer voting on what they have read.
+    async fn update_referendum_preimage_lookup(
+        &self,
+        db_referendum: &Referendum,
+        chain: &Chain,
+        maybe_preimage_lookup: Option<&ReferendumLookup>,
+    ) -> anyhow::Result<()> {
+        log::info!(
+            "Update {} referendum #{} preimage lookup: {:?} -> {:?}",
+            chain.display,
+            db_referendum.index,
+            db_referendum.preimage_lookup,
+            maybe_preimage_lookup,
+        );
+        self.postgres
+            .set_referendum_preimage_lookup(db_referendum.id, maybe_preimage_lookup)
+            .await?;
+        // referenda imported before the lookup was recorded have nothing to compare against
+        let Some(old_lookup) = db_referendum.preimage_lookup.as_ref() else {
+            return Ok(());
+        };
+        if db_referendum.is_archived {
+            return Ok(());
+        }
+        let (_, maybe_old_proposal) = self
+            .get_referendum_proposal(chain, Some(old_lookup))
+            .await?;
+        let (_, maybe_new_proposal) = self
+            .get_referendum_proposal(chain, maybe_preimage_lookup)
+            .await?;
+        let mut message = "⚠️ Referendum proposal has changed!".to_string();
+        message = format!(
+            "{message}\nOld preimage: `0x{}` ({} bytes)",
+            hex::encode(old_lookup.hash),
+            old_lookup.length,
+        );
+        if let Some(new_lookup) = maybe_preimage_lookup {
+            message = format!(
+                "{message}\nNew preimage: `0x{}` ({} bytes)",
+                hex::encode(new_lookup.hash),
+                new_lookup.length,
+            );
+        }
+        message = match (&maybe_old_proposal, &maybe_new_proposal) {
+            (Some(old_proposal), Some(new_proposal)) => format!(
+                "{message}\n\n*Changes:*\n{}",
+                format_proposal_diff(old_proposal, new_proposal),
+            ),
+            (None, Some(new_proposal)) => format!(
+                "{message}\n\nThe old preimage is no longer available.\n\n*New proposal:*\n{}",
+                format_proposal(new_proposal),
+            ),
+            (_, None) => format!("{message}\n\nThe new preimage is missing or cannot be decoded."),
+        };
+        self.telegram_client
+            .send_message(
+                db_referendum.telegram_chat_id,
+                Some(db_referendum.telegram_topic_id),
+                &message,
+                true,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn forward_signing_request(&self, request: &SigningRequest) -> anyhow::Result<()> {
+        let message = format!(
+            "✍️ {} extrinsic waiting for the signature of the proxy account:\n```\nrequest: {}\nsigner: {}\nspec version: {}\nmetadata hash: {}\ncall: {}\n```\nSign this payload:\n```\n{}\n```\nThen send `/signature {} <signature_hex>`.",
+            request.chain,
+            request.id,
+            request.signer_address,
+            request.spec_version,
+            request.metadata_hash,
+            request.call_hex,
+            request.payload_hex,
+            request.id,
+        );
+        self.telegram_client
+            .send_message(
+                CONFIG.telegram.chat_id,
+                Some(CONFIG.telegram.bot_chat_thread_id),
+                &message,
+                true,
+            )
+            .await?;
+        Ok(())
+    }
+
     async fn update_referendum_title(
         &self,
         db_referendum: &Referendum,
@@ -364,48 +594,71 @@ impl TelegramBot {
         }
         if !db_referendum.is_terminated && subsquare_referendum.state.status.requires_termination()
         {
-            let opensquare_referendum = if let Some(opensquare_referendum) = self
-                .opensquare_client
-                .fetch_referendum(&db_referendum.opensquare_cid)
-                .await?
-            {
-                opensquare_referendum
-            } else {
-                log::error!("Opensquare referendum not found - exit.");
-                return Ok(());
-            };
-            log::info!("New status requires termination.");
-            self.opensquare_client
-                .terminate_proposal(chain, &db_referendum.opensquare_cid)
-                .await?;
-            self.postgres.terminate_referendum(db_referendum.id).await?;
-            self.telegram_client
-                .send_message(
-                    db_referendum.telegram_chat_id,
-                    Some(db_referendum.telegram_topic_id),
-                    "OpenSquare referendum terminated.",
-                    true,
-                )
-                .await?;
-            let current_vote_count = self
-                .postgres
-                .get_referendum_vote_count(db_referendum.id)
-                .await?;
-            self.telegram_client
-                .update_referendum_topic_name(
-                    db_referendum.telegram_chat_id,
-                    db_referendum.telegram_topic_id,
-                    &opensquare_referendum.title,
-                    db_referendum.has_coi,
-                    Some(&subsquare_referendum.state.status.to_string().to_uppercase()),
-                    &format!("V{current_vote_count}"),
-                    db_referendum.status.get_status_icon(),
-                )
-                .await?;
+            let result = self
+                .auto_terminate_referendum(db_referendum, subsquare_referendum, chain)
+                .await;
+            self.save_audit_log_entry(
+                AUTO_VOTER_ACTOR,
+                None,
+                "/terminate",
+                &[subsquare_referendum.state.status.to_string()],
+                db_referendum.telegram_chat_id,
+                Some(db_referendum.telegram_topic_id),
+                &result,
+            )
+            .await;
+            result?;
         }
         Ok(())
     }
 
+    async fn auto_terminate_referendum(
+        &self,
+        db_referendum: &Referendum,
+        subsquare_referendum: &SubSquareReferendum,
+        chain: &Chain,
+    ) -> anyhow::Result<()> {
+        let opensquare_referendum = if let Some(opensquare_referendum) = self
+            .opensquare_client
+            .fetch_referendum(&db_referendum.opensquare_cid)
+            .await?
+        {
+            opensquare_referendum
+        } else {
+            log::error!("Opensquare referendum not found - exit.");
+            return Ok(());
+        };
+        log::info!("New status requires termination.");
+        self.opensquare_client
+            .terminate_proposal(chain, &db_referendum.opensquare_cid)
+            .await?;
+        self.postgres.terminate_referendum(db_referendum.id).await?;
+        self.telegram_client
+            .send_message(
+                db_referendum.telegram_chat_id,
+                Some(db_referendum.telegram_topic_id),
+                "OpenSquare referendum terminated.",
+                true,
+            )
+            .await?;
+        let current_vote_count = self
+            .postgres
+            .get_referendum_vote_count(db_referendum.id)
+            .await?;
+        self.telegram_client
+            .update_referendum_topic_name(
+                db_referendum.telegram_chat_id,
+                db_referendum.telegram_topic_id,
+                &opensquare_referendum.title,
+                db_referendum.has_coi,
+                Some(&subsquare_referendum.state.status.to_string().to_uppercase()),
+                &format!("V{current_vote_count}"),
+                db_referendum.status.get_status_icon(),
+            )
+            .await?;
+        Ok(())
+    }
+
     async fn import_referendum(
         &self,
         chain: &Chain,
@@ -420,14 +673,17 @@ impl TelegramBot {
             "DOT" => referendum.state.block.number,
             _ => get_polkadot_snapshot_height().await?,
         };
-        let preimage_exists = match self
+        let maybe_preimage_lookup = self
             .voter
             .get_referendum_lookup(chain, referendum.referendum_index)
-            .await?
-        {
-            Some(lookup) => self.voter.get_preimage(chain, &lookup).await?.is_some(),
-            None => false,
-        };
+            .await?;
+        let (preimage_exists, maybe_proposal) = self
+            .get_referendum_proposal(chain, maybe_preimage_lookup.as_ref())
+            .await?;
+        let audit_arguments = [
+            chain.token_ticker.to_lowercase(),
+            referendum.referendum_index.to_string(),
+        ];
         match self
             .referendum_importer
             .import_referendum(
@@ -435,10 +691,22 @@ impl TelegramBot {
                 referendum.referendum_index,
                 snapshot_height,
                 preimage_exists,
+                maybe_preimage_lookup.as_ref(),
+                maybe_proposal.as_ref(),
             )
             .await
         {
             Ok(db_referendum) => {
+                self.save_audit_log_entry(
+                    AUTO_VOTER_ACTOR,
+                    None,
+                    "/import",
+                    &audit_arguments,
+                    CONFIG.telegram.chat_id,
+                    Some(db_referendum.telegram_topic_id),
+                    &Ok(()),
+                )
+                .await;
                 self.telegram_client
                     .send_message(
                         CONFIG.telegram.chat_id,
@@ -464,13 +732,21 @@ impl TelegramBot {
                 if db_referendum.track == Track::SmallTipper
                     || db_referendum.track == Track::BigTipper
                 {
-                    self.process_force_vote_command(
-                        CONFIG.telegram.chat_id,
-                        Some(db_referendum.telegram_topic_id),
-                        CONFIG.voter.voting_admin_usernames.as_str(),
+                    let thread_id = Some(db_referendum.telegram_topic_id);
+                    let result = self
+                        .process_force_vote_command(CONFIG.telegram.chat_id, thread_id, None)
+                        .await;
+                    self.save_audit_log_entry(
+                        AUTO_VOTER_ACTOR,
                         None,
+                        "/forceabstain",
+                        &[],
+                        CONFIG.telegram.chat_id,
+                        thread_id,
+                        &result,
                     )
-                    .await?;
+                    .await;
+                    result?;
                 }
                 Ok(true)
             }
@@ -489,6 +765,16 @@ impl TelegramBot {
                         chain.display, referendum.referendum_index,
                     ),
                 };
+                self.save_audit_log_entry::<()>(
+                    AUTO_VOTER_ACTOR,
+                    None,
+                    "/import",
+                    &audit_arguments,
+                    CONFIG.telegram.chat_id,
+                    None,
+                    &Err(anyhow::anyhow!(message.clone())),
+                )
+                .await;
                 self.telegram_client
                     .send_message(CONFIG.telegram.chat_id, None, &message, true)
                     .await?;
@@ -499,54 +785,70 @@ impl TelegramBot {
     }
 
     async fn import_referenda(&self, chain: &Chain) -> anyhow::Result<()> {
-        let mut imported_referendum_count = 0;
+        let mut referenda = Vec::new();
         for page in 1..=4 {
-            let referenda = self
+            let subsquare_referenda = self
                 .subsquare_client
                 .fetch_referenda(chain, page, 30)
                 .await?;
-            for subsquare_referendum in referenda.items.iter() {
+            for subsquare_referendum in subsquare_referenda.items {
                 let maybe_db_referendum = self
                     .postgres
                     .get_referendum_by_index(chain.id, subsquare_referendum.referendum_index)
                     .await?;
-                if let Some(db_referendum) = maybe_db_referendum.as_ref() {
-                    let preimage_exists = match self
-                        .voter
-                        .get_referendum_lookup(chain, db_referendum.index)
-                        .await?
-                    {
-                        Some(lookup) => self.voter.get_preimage(chain, &lookup).await?.is_some(),
-                        None => false,
-                    };
-                    if subsquare_referendum.state.status.is_ongoing()
-                        && db_referendum.preimage_exists != preimage_exists
+                referenda.push((subsquare_referendum, maybe_db_referendum));
+            }
+        }
+        // preimages only matter while the referendum is active
+        let active_referendum_indices: Vec<u32> = referenda
+            .iter()
+            .filter(|(subsquare_referendum, maybe_db_referendum)| {
+                maybe_db_referendum.is_some() && subsquare_referendum.state.status.is_ongoing()
+            })
+            .map(|(subsquare_referendum, _)| subsquare_referendum.referendum_index)
+            .collect();
+        let preimage_states = self
+            .voter
+            .get_preimage_states(chain, &active_referendum_indices)
+            .await?;
+        let mut imported_referendum_count = 0;
+        for (subsquare_referendum, maybe_db_referendum) in referenda.iter() {
+            if let Some(db_referendum) = maybe_db_referendum.as_ref() {
+                if let Some((maybe_preimage_lookup, preimage_exists)) =
+                    preimage_states.get(&db_referendum.index)
+                {
+                    if maybe_preimage_lookup.is_some()
+                        && db_referendum.preimage_lookup != *maybe_preimage_lookup
                     {
-                        self.update_referendum_preimage_exists(
+                        self.update_referendum_preimage_lookup(
                             db_referendum,
                             chain,
-                            preimage_exists,
+                            maybe_preimage_lookup.as_ref(),
                         )
                         .await?;
                     }
-                    if db_referendum.title != subsquare_referendum.title {
-                        self.update_referendum_title(
+                    if db_referendum.preimage_exists != *preimage_exists {
+                        self.update_referendum_preimage_exists(
                             db_referendum,
                             chain,
-                            &subsquare_referendum.title,
+                            *preimage_exists,
                         )
                         .await?;
                     }
-                    if db_referendum.status != subsquare_referendum.state.status {
-                        self.update_referendum_status(db_referendum, subsquare_referendum, chain)
-                            .await?;
-                    }
-                } else if (ReferendumStatus::Deciding == subsquare_referendum.state.status
-                    || ReferendumStatus::Confirming == subsquare_referendum.state.status)
-                    && self.import_referendum(chain, subsquare_referendum).await?
-                {
-                    imported_referendum_count += 1;
                 }
+                if db_referendum.title != subsquare_referendum.title {
+                    self.update_referendum_title(db_referendum, chain, &subsquare_referendum.title)
+                        .await?;
+                }
+                if db_referendum.status != subsquare_referendum.state.status {
+                    self.update_referendum_status(db_referendum, subsquare_referendum, chain)
+                        .await?;
+                }
+            } else if (ReferendumStatus::Deciding == subsquare_referendum.state.status
+                || ReferendumStatus::Confirming == subsquare_referendum.state.status)
+                && self.import_referendum(chain, subsquare_referendum).await?
+            {
+                imported_referendum_count += 1;
             }
         }
         log::info!("Imported {imported_referendum_count} referenda.");
@@ -556,6 +858,7 @@ impl TelegramBot {
     #[allow(clippy::cognitive_complexity)]
     async fn update_votes(&self, chain: &Chain) -> anyhow::Result<()> {
         let members = self.postgres.get_all_members(false).await?;
+        let members_including_on_leave = self.postgres.get_all_members(true).await?;
         let db_referenda = self
             .postgres
             .get_referenda_by_statuses(chain.id, &ReferendumStatus::get_ongoing())
@@ -703,21 +1006,20 @@ impl TelegramBot {
                 }
             }
             let mut submit_vote = false;
-            let vote_counts = get_vote_counts(members.len() as u32, &opensquare_votes);
-            let (evaluation, _) =
-                Policy::policy_for_track(&db_referendum.track).evaluate(&vote_counts);
+            let vote_counts = get_vote_counts(&members_including_on_leave, &opensquare_votes);
+            let subsquare_referendum =
+                require_subsquare_referendum(&self.subsquare_client, chain, db_referendum.index)
+                    .await?;
+            let (evaluation, _) = self
+                .voting_policy
+                .policy_for_track(&db_referendum.track)
+                .evaluate(&vote_counts, get_blocks_left(&subsquare_referendum));
             if let Some(last_vote) = &last_vote {
-                let previous_vote = get_vote_name(last_vote.vote);
-                let new_vote = get_vote_name(evaluation.simplify()?);
-                if last_vote.vote != evaluation.simplify()? {
+                let previous_vote = last_vote.on_chain_vote();
+                let new_vote = evaluation.on_chain_vote()?;
+                if previous_vote != new_vote {
                     feedback.push(format!("• Outcome changed {previous_vote} -> {new_vote}."));
                     log::info!("Outcome changed {previous_vote} -> {new_vote}.");
-                    let subsquare_referendum = require_subsquare_referendum(
-                        &self.subsquare_client,
-                        chain,
-                        db_referendum.index,
-                    )
-                    .await?;
                     if subsquare_referendum.state.status == ReferendumStatus::Confirming
                         && !vote_change_is_positive(last_vote.vote, evaluation.simplify()?)
                     {
@@ -732,40 +1034,31 @@ impl TelegramBot {
                         submit_vote = true;
                     }
                 } else {
-                    let vote = get_vote_name(last_vote.vote);
+                    let vote = last_vote.on_chain_vote();
                     feedback.push(format!("• Outcome is still {vote}."));
                     feedback.push("ℹ️ Not submitting a vote.".to_string());
                     log::info!("Outcome is still {vote}.",);
                 }
-            } else if evaluation.is_no_vote() {
-                feedback.push("• Partipation threshold not met yet.".to_string());
-                feedback.push("ℹ️ Not submitting a vote.".to_string());
-                log::info!("Participation threshold not met. Not submitting a vote.");
-            } else if (db_referendum.track == Track::SmallSpender
-                || db_referendum.track == Track::BigTipper)
-                && opensquare_votes.len() < 3
+            } else if let PolicyEvaluation::MinimumVoteCountNotMet {
+                minimum_vote_count, ..
+            } = evaluation
             {
                 let message = format!(
-                    "ℹ️ {} - will wait until 3 votes.",
-                    db_referendum.track.name()
-                );
-                log::info!("{message}");
-                feedback.push(message);
-            } else if db_referendum.track == Track::SmallTipper && opensquare_votes.len() < 2 {
-                let message = format!(
-                    "ℹ️ {} - will wait until 2 votes.",
+                    "ℹ️ {} - will wait until {minimum_vote_count} votes.",
                     db_referendum.track.name()
                 );
                 log::info!("{message}");
                 feedback.push(message);
+            } else if let PolicyEvaluation::CoreQuorumNotMet { .. } = evaluation {
+                feedback.push("• Core participation threshold not met yet.".to_string());
+                feedback.push("ℹ️ Not submitting a vote.".to_string());
+                log::info!("Core participation threshold not met. Not submitting a vote.");
+            } else if evaluation.is_no_vote() {
+                feedback.push("• Partipation threshold not met yet.".to_string());
+                feedback.push("ℹ️ Not submitting a vote.".to_string());
+                log::info!("Participation threshold not met. Not submitting a vote.");
             } else {
-                let vote = get_vote_name(evaluation.simplify()?);
-                let subsquare_referendum = require_subsquare_referendum(
-                    &self.subsquare_client,
-                    chain,
-                    db_referendum.index,
-                )
-                .await?;
+                let vote = evaluation.on_chain_vote()?;
                 if subsquare_referendum.state.status == ReferendumStatus::Confirming
                     && !evaluation.simplify()?.unwrap_or(true)
                 {
@@ -792,25 +1085,257 @@ impl TelegramBot {
                         )
                         .await?;
                 }
-                if submit_vote {
-                    self.process_vote_command(
-                        db_referendum.telegram_chat_id,
-                        Some(db_referendum.telegram_topic_id),
-                        &CONFIG.voter.voting_admin_usernames,
-                        true,
+                if submit_vote && self.voter.is_safe_mode() {
+                    let outcome = evaluation.on_chain_vote()?;
+                    let previous_outcome = self
+                        .simulated_votes
+                        .lock()
+                        .unwrap()
+                        .insert(db_referendum.id, outcome);
+                    if previous_outcome == Some(outcome) {
+                        log::info!("Safe mode, outcome is still {outcome}. Skip simulation.");
+                    } else {
+                        let chat_id = db_referendum.telegram_chat_id;
+                        let thread_id = Some(db_referendum.telegram_topic_id);
+                        let result = self.process_simulate_vote_command(chat_id, thread_id).await;
+                        self.save_audit_log_entry(
+                            AUTO_VOTER_ACTOR,
+                            None,
+                            "/simulatevote",
+                            &[],
+                            chat_id,
+                            thread_id,
+                            &result,
+                        )
+                        .await;
+                        result?;
+                    }
+                } else if submit_vote {
+                    let chat_id = db_referendum.telegram_chat_id;
+                    let thread_id = Some(db_referendum.telegram_topic_id);
+                    let result = self
+                        .process_vote_command(chat_id, thread_id, true, true)
+                        .await;
+                    self.save_audit_log_entry(
+                        AUTO_VOTER_ACTOR,
+                        None,
+                        "/vote",
+                        &[],
+                        chat_id,
+                        thread_id,
+                        &result,
                     )
-                    .await?;
-                    self.process_notify_command(
-                        db_referendum.telegram_chat_id,
-                        Some(db_referendum.telegram_topic_id),
-                        &CONFIG.voter.voting_admin_usernames,
+                    .await;
+                    result?;
+                    let result = self.process_notify_command(chat_id, thread_id).await;
+                    self.save_audit_log_entry(
+                        AUTO_VOTER_ACTOR,
+                        None,
+                        "/notify",
+                        &[],
+                        chat_id,
+                        thread_id,
+                        &result,
                     )
-                    .await?;
+                    .await;
+                    result?;
                 }
             }
         }
         Ok(())
     }
+
+    /// Alerts the bot thread when the proxy account can no longer pay for the configured
+    /// number of votes.
+    async fn check_proxy_balance(&self, chain: &Chain) -> anyhow::Result<()> {
+        let (proxy_account_id, available_balance) =
+            self.voter.get_proxy_account_balance(chain).await?;
+        let vote_fee = self.voter.estimate_vote_fee(chain).await?;
+        let required_balance = vote_fee * CONFIG.voter.proxy_balance_alert_vote_count as u128;
+        log::info!(
+            "{} proxy account has {}, {} required.",
+            chain.display,
+            chain.format_balance(available_balance),
+            chain.format_balance(required_balance),
+        );
+        if available_balance < required_balance {
+            let message = format!(
+                "⚠️ {} proxy account {} has {} available, which covers fewer than {} votes at an estimated {} per vote. Please top it up.",
+                chain.display,
+                proxy_account_id.to_ss58_check_with_version(chain.ss58_prefix),
+                chain.format_balance(available_balance),
+                CONFIG.voter.proxy_balance_alert_vote_count,
+                chain.format_balance(vote_fee),
+            );
+            self.telegram_client
+                .send_message(
+                    CONFIG.telegram.chat_id,
+                    Some(CONFIG.telegram.bot_chat_thread_id),
+                    &message,
+                    true,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Alerts the bot thread when the live runtime no longer matches the metadata the voter was
+    /// compiled against. The voter refuses to submit until it matches again.
+    async fn check_metadata(&self, chain: &Chain) -> anyhow::Result<()> {
+        let metadata_check = self.voter.check_metadata(chain).await?;
+        if !metadata_check.has_drift() {
+            log::info!(
+                "{} runtime {} matches the compiled metadata.",
+                chain.display,
+                metadata_check.spec_version,
+            );
+            return Ok(());
+        }
+        log::error!(
+            "{} runtime {} does not match the compiled metadata: {}",
+            chain.display,
+            metadata_check.spec_version,
+            metadata_check.mismatches.join(" "),
+        );
+        let message = format!(
+            "🚨 {} runtime {} does not match the metadata the voter was compiled against. Voting is blocked until the metadata is updated.\n```\n{}\n```",
+            chain.display,
+            metadata_check.spec_version,
+            metadata_check.mismatches.join("\n"),
+        );
+        self.telegram_client
+            .send_message(
+                CONFIG.telegram.chat_id,
+                Some(CONFIG.telegram.bot_chat_thread_id),
+                &message,
+                true,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Posts vote record discrepancies to the referendum topics. `reported` keeps the last
+    /// posted discrepancies per referendum, so that a topic is only notified when they change.
+    async fn check_vote_records(
+        &self,
+        chain: &Chain,
+        reported: &mut BTreeMap<u32, Vec<String>>,
+    ) -> anyhow::Result<()> {
+        let db_referenda = self
+            .postgres
+            .get_referenda_by_statuses(chain.id, &ReferendumStatus::get_ongoing())
+            .await?;
+        for db_referendum in db_referenda.iter().filter(|r| !r.is_terminated) {
+            let reconciliation = self.reconcile_votes(chain, db_referendum).await?;
+            if reconciliation.discrepancies.is_empty() {
+                reported.remove(&db_referendum.id);
+                continue;
+            }
+            if reported.get(&db_referendum.id) == Some(&reconciliation.discrepancies) {
+                continue;
+            }
+            log::warn!(
+                "{} referendum {} vote record doesn't match the chain.",
+                chain.display,
+                db_referendum.index,
+            );
+            let message = format!(
+                "⚠️ The recorded vote doesn't match the on-chain votes.\n{}\nUse /reconcile to update the record from the chain.",
+                reconciliation.discrepancies.join("\n"),
+            );
+            self.telegram_client
+                .send_message(
+                    db_referendum.telegram_chat_id,
+                    Some(db_referendum.telegram_topic_id),
+                    &message,
+                    true,
+                )
+                .await?;
+            reported.insert(db_referendum.id, reconciliation.discrepancies);
+        }
+        Ok(())
+    }
+
+    /// Removes the voting accounts' votes on finished referenda once their locks expire, unlocks
+    /// the classes and marks the votes as cleaned up.
+    async fn unlock_expired_votes(&self, chain: &Chain) -> anyhow::Result<()> {
+        let plan = self.voter.get_unlock_plan(chain).await?;
+        if plan.is_empty() {
+            log::info!("No expired {} votes to unlock.", chain.display);
+            return Ok(());
+        }
+        if self.voter.is_safe_mode() {
+            let dry_run = self.voter.dry_run_unlock(chain, &plan).await?;
+            log::info!(
+                "Safe mode is on, skip unlocking {} expired {} votes: {}",
+                plan.expired_votes.len(),
+                chain.display,
+                dry_run.summary.join(" "),
+            );
+            return Ok(());
+        }
+        let mut referendum_ids = Vec::new();
+        for expired_vote in plan.expired_votes.iter() {
+            if let Some(db_referendum) = self
+                .postgres
+                .get_referendum_by_index(chain.id, expired_vote.referendum_index)
+                .await?
+            {
+                referendum_ids.push(db_referendum.id);
+            }
+        }
+        referendum_ids.sort();
+        referendum_ids.dedup();
+        let Some(_lock) = self.postgres.try_lock_referenda(&referendum_ids).await? else {
+            log::info!(
+                "Skip unlocking {} votes, a referendum is locked by another action.",
+                chain.display
+            );
+            return Ok(());
+        };
+        let result = self.voter.unlock(chain, &plan).await;
+        self.save_audit_log_entry(
+            AUTO_VOTER_ACTOR,
+            None,
+            "unlock",
+            &[chain.token_ticker.to_lowercase()],
+            CONFIG.telegram.chat_id,
+            Some(CONFIG.telegram.bot_chat_thread_id),
+            &result,
+        )
+        .await;
+        let (_block_hash, block_number, extrinsic_index) = result?;
+        let mut referendum_indices: Vec<u32> = plan
+            .expired_votes
+            .iter()
+            .map(|expired_vote| expired_vote.referendum_index)
+            .collect();
+        referendum_indices.sort();
+        referendum_indices.dedup();
+        for referendum_index in referendum_indices.iter() {
+            self.postgres
+                .set_referendum_votes_cleaned_up(chain.id, *referendum_index)
+                .await?;
+        }
+        let message = format!(
+            "🔓 Removed {} expired {} vote(s) and unlocked {} class lock(s).\nhttps://assethub-{}.subscan.io/extrinsic/{}-{}",
+            plan.expired_votes.len(),
+            chain.display,
+            plan.unlocks.len(),
+            chain.chain.to_lowercase(),
+            block_number,
+            extrinsic_index,
+        );
+        self.telegram_client
+            .send_message(
+                CONFIG.telegram.chat_id,
+                Some(CONFIG.telegram.bot_chat_thread_id),
+                &message,
+                false,
+            )
+            .await?;
+        Ok(())
+    }
 }
 
 #[async_trait(? Send)]
@@ -825,16 +1350,16 @@ impl Service for TelegramBot {
     async fn run(&'static self) -> anyhow::Result<()> {
         log::info!("Telegram bot started.");
         let mut offset: Option<i64> = None;
+        if let Err(err) = self.recover_vote_intents().await {
+            log::error!("Vote intent recovery failed: {err}");
+        }
 
         tokio::spawn(async move {
-            let polkadot = Chain::polkadot();
-            let kusama = Chain::kusama();
             loop {
-                if let Err(err) = self.update_votes(&polkadot).await {
-                    log::error!("Polkadot auto-vote update failed: {err}");
-                }
-                if let Err(err) = self.update_votes(&kusama).await {
-                    log::error!("Kusama auto-vote update failed: {err}");
+                for chain in self.chains.iter() {
+                    if let Err(err) = self.update_votes(chain).await {
+                        log::error!("{chain} auto-vote update failed: {err}");
+                    }
                 }
                 log::info!("Sleep for {} seconds.", CONFIG.voter.sleep_seconds);
                 tokio::time::sleep(std::time::Duration::from_secs(CONFIG.voter.sleep_seconds))
@@ -843,27 +1368,121 @@ impl Service for TelegramBot {
         });
 
         tokio::spawn(async move {
-            let polkadot = Chain::polkadot();
-            let kusama = Chain::kusama();
             loop {
-                if let Err(err) = self.import_referenda(&polkadot).await {
-                    log::error!("Import Polkadot referenda failed: {err}");
-                }
-                if let Err(err) = self.import_referenda(&kusama).await {
-                    log::error!("Import Kusama referenda failed: {err}");
+                for chain in self.chains.iter() {
+                    if let Err(err) = self.import_referenda(chain).await {
+                        log::error!("{chain} referendum import failed: {err}");
+                    }
                 }
                 log::info!("Sleep for {} seconds.", CONFIG.voter.sleep_seconds);
                 tokio::time::sleep(std::time::Duration::from_secs(CONFIG.voter.sleep_seconds))
                     .await;
             }
         });
+
+        tokio::spawn(async move {
+            loop {
+                for chain in self.chains.iter() {
+                    if let Err(err) = self.check_proxy_balance(chain).await {
+                        log::error!("{chain} proxy balance check failed: {err}");
+                    }
+                }
+                log::info!(
+                    "Sleep for {} seconds.",
+                    CONFIG.voter.proxy_balance_check_seconds
+                );
+                tokio::time::sleep(std::time::Duration::from_secs(
+                    CONFIG.voter.proxy_balance_check_seconds,
+                ))
+                .await;
+            }
+        });
+        tokio::spawn(async move {
+            loop {
+                for chain in self.chains.iter() {
+                    if let Err(err) = self.check_metadata(chain).await {
+                        log::error!("{chain} metadata check failed: {err}");
+                    }
+                }
+                log::info!("Sleep for {} seconds.", CONFIG.voter.metadata_check_seconds);
+                tokio::time::sleep(std::time::Duration::from_secs(
+                    CONFIG.voter.metadata_check_seconds,
+                ))
+                .await;
+            }
+        });
+        tokio::spawn(async move {
+            let mut reported = BTreeMap::new();
+            loop {
+                for chain in self.chains.iter() {
+                    if let Err(err) = self.check_vote_records(chain, &mut reported).await {
+                        log::error!("{chain} vote reconciliation failed: {err}");
+                    }
+                }
+                log::info!("Sleep for {} seconds.", CONFIG.voter.reconciliation_seconds);
+                tokio::time::sleep(std::time::Duration::from_secs(
+                    CONFIG.voter.reconciliation_seconds,
+                ))
+                .await;
+            }
+        });
+        tokio::spawn(async move {
+            loop {
+                for chain in self.chains.iter() {
+                    if let Err(err) = self.unlock_expired_votes(chain).await {
+                        log::error!("{chain} expired vote unlock failed: {err}");
+                    }
+                }
+                log::info!("Sleep for {} seconds.", CONFIG.voter.unlock_check_seconds);
+                tokio::time::sleep(std::time::Duration::from_secs(
+                    CONFIG.voter.unlock_check_seconds,
+                ))
+                .await;
+            }
+        });
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = self.deliver_outbox_items().await {
+                    log::error!("Outbox delivery failed: {err}");
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(CONFIG.outbox.poll_seconds))
+                    .await;
+            }
+        });
+        if self.voter.is_offline_signing() {
+            let mut signing_requests = self.voter.subscribe_signing_requests();
+            tokio::spawn(async move {
+                while let Ok(request) = signing_requests.recv().await {
+                    if let Err(err) = self.forward_signing_request(&request).await {
+                        log::error!("Forwarding signing request {} failed: {err}", request.id);
+                    }
+                }
+            });
+        }
+        let signing_permits = Arc::new(Semaphore::new(MAX_SIGNING_COMMANDS));
         loop {
             let result = self.telegram_client.get_updates(offset).await;
             match result {
                 Ok(updates) => {
                     for update in updates {
                         offset = Some((update.update_id + 1).into());
-                        self.process_update(&update).await;
+                        let may_wait_for_signature = self.voter.is_offline_signing()
+                            && get_update_command(&update)
+                                .is_some_and(|command| may_wait_for_signature(&command));
+                        if !may_wait_for_signature {
+                            self.process_update(&update).await;
+                            continue;
+                        }
+                        // a command waiting for a signature must not block `/signature`
+                        match signing_permits.clone().try_acquire_owned() {
+                            Ok(permit) => {
+                                tokio::spawn(async move {
+                                    self.process_update(&update).await;
+                                    drop(permit);
+                                });
+                            }
+                            Err(_) => self.reject_signing_update(&update).await,
+                        }
                     }
                 }
                 Err(error) => {
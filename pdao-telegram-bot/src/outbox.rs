@@ -0,0 +1,150 @@
+use crate::{TelegramBot, CONFIG};
+use pdao_types::outbox::{OutboxEffect, OutboxItem};
+use pdao_types::substrate::chain::Chain;
+
+/// Doubles the delay after each failed attempt, up to the configured maximum.
+fn get_backoff_seconds(attempt_count: u32) -> u64 {
+    let factor = 2u64.saturating_pow(attempt_count.saturating_sub(1));
+    CONFIG
+        .outbox
+        .initial_backoff_seconds
+        .saturating_mul(factor)
+        .min(CONFIG.outbox.max_backoff_seconds)
+}
+
+impl TelegramBot {
+    async fn deliver_outbox_effect(&self, item: &OutboxItem) -> anyhow::Result<()> {
+        match &item.effect {
+            OutboxEffect::TelegramMessage {
+                chat_id,
+                thread_id,
+                message,
+            } => {
+                self.telegram_client
+                    .send_message(*chat_id, *thread_id, message, true)
+                    .await?;
+            }
+            OutboxEffect::TelegramTopicName {
+                chat_id,
+                thread_id,
+                name,
+                has_coi,
+                vote_count_status,
+                status_emoji,
+            } => {
+                self.telegram_client
+                    .update_referendum_topic_name(
+                        *chat_id,
+                        *thread_id,
+                        name,
+                        *has_coi,
+                        None,
+                        vote_count_status,
+                        status_emoji,
+                    )
+                    .await?;
+            }
+            OutboxEffect::OpenSquareAppendant {
+                network_id,
+                cid,
+                content,
+            } => {
+                self.opensquare_client
+                    .make_appendant_on_proposal(&Chain::from_id(*network_id), cid, content)
+                    .await?;
+            }
+            OutboxEffect::SubSquareComment {
+                network_id,
+                referendum_index,
+                proposed_height,
+                reply_to_comment_cid,
+                content,
+                vote_id,
+                chat_id,
+                thread_id,
+            } => {
+                let chain = Chain::from_id(*network_id);
+                let response = if let Some(comment_cid) = reply_to_comment_cid {
+                    self.subsquare_client
+                        .post_comment_reply(&chain, *referendum_index, comment_cid, content)
+                        .await?
+                } else {
+                    self.subsquare_client
+                        .post_comment(&chain, *referendum_index, *proposed_height, content)
+                        .await?
+                };
+                self.postgres
+                    .set_vote_subsquare_comment(*vote_id, &response.cid, response.index)
+                    .await?;
+                self.postgres
+                    .enqueue_outbox_item(
+                        &format!("{}-announcement", item.idempotency_key),
+                        &OutboxEffect::TelegramMessage {
+                            chat_id: *chat_id,
+                            thread_id: Some(*thread_id),
+                            message: format!(
+                                "💬 Feedback posted on SubSquare.\nhttps://{}.subsquare.io/referenda/{referendum_index}#{}",
+                                chain.chain.to_lowercase(),
+                                response.index,
+                            ),
+                        },
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delivers the due outbox items, retrying the failed ones with exponential backoff and
+    /// reporting them to the bot thread once they run out of attempts.
+    pub(crate) async fn deliver_outbox_items(&self) -> anyhow::Result<()> {
+        while let Some(item) = self
+            .postgres
+            .claim_outbox_item(CONFIG.outbox.lease_seconds)
+            .await?
+        {
+            let error = match self.deliver_outbox_effect(&item).await {
+                Ok(()) => {
+                    self.postgres.set_outbox_item_delivered(item.id).await?;
+                    continue;
+                }
+                Err(error) => error.to_string(),
+            };
+            if item.attempt_count < CONFIG.outbox.max_attempts {
+                let backoff_seconds = get_backoff_seconds(item.attempt_count);
+                log::warn!(
+                    "{} {} failed on attempt {}, retry in {backoff_seconds} seconds: {error}",
+                    item.effect.get_name(),
+                    item.idempotency_key,
+                    item.attempt_count,
+                );
+                self.postgres
+                    .set_outbox_item_retry(item.id, backoff_seconds, &error)
+                    .await?;
+                continue;
+            }
+            log::error!(
+                "{} {} failed permanently: {error}",
+                item.effect.get_name(),
+                item.idempotency_key,
+            );
+            self.postgres
+                .set_outbox_item_failed(item.id, &error)
+                .await?;
+            self.telegram_client
+                .send_message(
+                    CONFIG.telegram.chat_id,
+                    Some(CONFIG.telegram.bot_chat_thread_id),
+                    &format!(
+                        "🚨 {} `{}` failed after {} attempts:\n```\n{error}\n```",
+                        item.effect.get_name(),
+                        item.idempotency_key,
+                        item.attempt_count,
+                    ),
+                    true,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
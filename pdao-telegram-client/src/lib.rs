@@ -1,9 +1,13 @@
 use frankenstein::methods::{
-    CreateForumTopicParams, DeleteForumTopicParams, EditForumTopicParams, GetUpdatesParams,
-    SendDocumentParams, SendMessageParams,
+    AnswerCallbackQueryParams, CreateForumTopicParams, DeleteForumTopicParams,
+    EditForumTopicParams, EditMessageReplyMarkupParams, GetUpdatesParams, SendDocumentParams,
+    SendMessageParams,
 };
 use frankenstein::response::MethodResponse;
-use frankenstein::types::{AllowedUpdate, ChatId, LinkPreviewOptions, Message};
+use frankenstein::types::{
+    AllowedUpdate, ChatId, InlineKeyboardButton, InlineKeyboardMarkup, LinkPreviewOptions, Message,
+    ReplyMarkup,
+};
 use frankenstein::updates::Update;
 use frankenstein::{client_reqwest::Bot, AsyncTelegramApi, ParseMode};
 use pdao_config::Config;
@@ -11,6 +15,78 @@ use pdao_types::governance::opensquare::OpenSquareNewProposalResponse;
 use pdao_types::governance::subsquare::SubSquareReferendum;
 use pdao_types::governance::track::Track;
 use pdao_types::substrate::chain::Chain;
+use pdao_types::substrate::referendum::ProposalCall;
+
+/// Keeps long batches from pushing the intro message over the Telegram length limit.
+const MAX_PROPOSAL_LINES: usize = 32;
+
+fn format_proposal_lines(mut lines: Vec<String>) -> String {
+    if lines.len() > MAX_PROPOSAL_LINES {
+        let omitted = lines.len() - MAX_PROPOSAL_LINES + 1;
+        lines.truncate(MAX_PROPOSAL_LINES - 1);
+        lines.push(format!("… {omitted} more lines"));
+    }
+    format!("```\n{}\n```", lines.join("\n").replace('`', "'"))
+}
+
+/// Renders the decoded proposal call tree as a Markdown code block.
+pub fn format_proposal(proposal: &ProposalCall) -> String {
+    format_proposal_lines(proposal.to_lines())
+}
+
+/// Renders the call-level diff between two versions of a proposal as a Markdown code block.
+pub fn format_proposal_diff(old_proposal: &ProposalCall, new_proposal: &ProposalCall) -> String {
+    format_proposal_lines(old_proposal.get_diff_lines(new_proposal))
+}
+
+/// Callback data of the confirmation button is the confirmed command with this prefix.
+pub const CONFIRM_CALLBACK_PREFIX: &str = "confirm:";
+pub const CANCEL_CALLBACK_DATA: &str = "cancel";
+
+fn get_callback_button(text: &str, callback_data: &str) -> InlineKeyboardButton {
+    InlineKeyboardButton::builder()
+        .text(text)
+        .callback_data(callback_data)
+        .build()
+}
+
+/// Buttons on the topic intro and status messages. The callback data of each button is the
+/// command it runs.
+pub fn get_referendum_keyboard() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup {
+        inline_keyboard: vec![
+            vec![
+                get_callback_button("🔄 Refresh status", "/status"),
+                get_callback_button("🔔 Notify non-voters", "/notify"),
+            ],
+            vec![
+                get_callback_button("👍 Force Aye", "/forceaye"),
+                get_callback_button("👎 Force Nay", "/forcenay"),
+                get_callback_button("⚪ Force Abstain", "/forceabstain"),
+            ],
+            vec![get_callback_button("🏁 Terminate", "/terminate")],
+        ],
+    }
+}
+
+/// Returns the label of the referendum keyboard button that runs the command, if any.
+pub fn get_referendum_keyboard_button_text(command: &str) -> Option<String> {
+    get_referendum_keyboard()
+        .inline_keyboard
+        .into_iter()
+        .flatten()
+        .find(|button| button.callback_data.as_deref() == Some(command))
+        .map(|button| button.text)
+}
+
+pub fn get_confirmation_keyboard(command: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup {
+        inline_keyboard: vec![vec![
+            get_callback_button("✅ Confirm", &format!("{CONFIRM_CALLBACK_PREFIX}{command}")),
+            get_callback_button("✖️ Cancel", CANCEL_CALLBACK_DATA),
+        ]],
+    }
+}
 
 pub struct TelegramClient {
     telegram_api: Bot,
@@ -45,6 +121,7 @@ impl TelegramClient {
         config: &Config,
         referendum: &SubSquareReferendum,
         preimage_exists: bool,
+        maybe_proposal: Option<&ProposalCall>,
         new_opensquare_proposal_response: &OpenSquareNewProposalResponse,
     ) -> anyhow::Result<(i32, i32)> {
         log::info!(
@@ -98,6 +175,11 @@ impl TelegramClient {
                 "⚪ No preimage"
             },
         );
+        let message = if let Some(proposal) = maybe_proposal {
+            format!("{message}\n\n*Proposal:*\n{}", format_proposal(proposal))
+        } else {
+            message
+        };
         let message = if let Some(content_summary) = &referendum.content_summary {
             if let Some(summary) = &content_summary.summary {
                 format!(
@@ -117,11 +199,12 @@ impl TelegramClient {
             new_opensquare_proposal_response.cid,
         );
         let send_message_response = self
-            .send_message(
+            .send_message_with_reply_markup(
                 config.telegram.chat_id,
                 Some(create_topic_response.result.message_thread_id),
                 &message,
                 true,
+                Some(get_referendum_keyboard()),
             )
             .await?;
         log::info!(
@@ -142,6 +225,18 @@ impl TelegramClient {
         thread_id: Option<i32>,
         message: &str,
         enable_notification: bool,
+    ) -> anyhow::Result<MethodResponse<Message>> {
+        self.send_message_with_reply_markup(chat_id, thread_id, message, enable_notification, None)
+            .await
+    }
+
+    pub async fn send_message_with_reply_markup(
+        &self,
+        chat_id: i64,
+        thread_id: Option<i32>,
+        message: &str,
+        enable_notification: bool,
+        reply_markup: Option<InlineKeyboardMarkup>,
     ) -> anyhow::Result<MethodResponse<Message>> {
         let response = self
             .telegram_api
@@ -167,12 +262,45 @@ impl TelegramClient {
                 message_effect_id: None,
                 suggested_post_parameters: None,
                 reply_parameters: None,
-                reply_markup: None,
+                reply_markup: reply_markup.map(ReplyMarkup::InlineKeyboardMarkup),
             })
             .await?;
         Ok(response)
     }
 
+    pub async fn remove_reply_markup(&self, chat_id: i64, message_id: i32) -> anyhow::Result<()> {
+        self.telegram_api
+            .edit_message_reply_markup(&EditMessageReplyMarkupParams {
+                business_connection_id: None,
+                chat_id: Some(ChatId::Integer(chat_id)),
+                message_id: Some(message_id),
+                inline_message_id: None,
+                reply_markup: None,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Stops the loading indicator on the pressed button, optionally showing the text to the
+    /// user who pressed it.
+    pub async fn answer_callback_query(
+        &self,
+        callback_query_id: &str,
+        text: Option<&str>,
+        show_alert: bool,
+    ) -> anyhow::Result<()> {
+        self.telegram_api
+            .answer_callback_query(&AnswerCallbackQueryParams {
+                callback_query_id: callback_query_id.to_string(),
+                text: text.map(|text| text.chars().take(200).collect()),
+                show_alert: Some(show_alert),
+                url: None,
+                cache_time: None,
+            })
+            .await?;
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn update_referendum_topic_name(
         &self,
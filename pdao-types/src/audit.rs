@@ -0,0 +1,20 @@
+use chrono::NaiveDateTime;
+
+/// Actor of the actions taken by the background loops.
+pub const AUTO_VOTER_ACTOR: &str = "auto-voter";
+
+/// A bot command or automated action, with its outcome.
+#[derive(Clone, Debug)]
+pub struct AuditLogEntry {
+    pub id: u32,
+    pub actor: String,
+    pub actor_telegram_user_id: Option<i64>,
+    pub command: String,
+    pub arguments: Vec<String>,
+    pub referendum_id: Option<u32>,
+    pub telegram_chat_id: Option<i64>,
+    pub telegram_thread_id: Option<i32>,
+    pub is_success: bool,
+    pub error: Option<String>,
+    pub created_at: NaiveDateTime,
+}
@@ -1,5 +1,7 @@
+use crate::governance::policy::{Policy, PolicyEvaluation, VoteCounts};
 use crate::governance::track::Track;
 use crate::substrate::account_id::AccountId;
+use crate::substrate::referendum::ReferendumLookup;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -138,6 +140,222 @@ pub struct Referendum {
     pub has_coi: bool,
     pub is_archived: bool,
     pub preimage_exists: bool,
+    pub preimage_lookup: Option<ReferendumLookup>,
+}
+
+/// Shares of the voting balance to put on each side of a split vote.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VoteSplit {
+    pub ayes: u32,
+    pub nays: u32,
+    pub abstains: u32,
+}
+
+impl VoteSplit {
+    fn total(&self) -> u64 {
+        self.ayes as u64 + self.nays as u64 + self.abstains as u64
+    }
+
+    /// Divides the balance into aye, nay and abstain balances in proportion to the shares.
+    /// The rounding remainder goes to abstain, or to nay when there are no abstains.
+    pub fn split_balance(&self, balance: u128) -> (u128, u128, u128) {
+        let total = self.total() as u128;
+        if total == 0 {
+            return (0, 0, balance);
+        }
+        let aye = balance / total * self.ayes as u128 + balance % total * self.ayes as u128 / total;
+        if self.abstains == 0 {
+            return (aye, balance - aye, 0);
+        }
+        let nay = balance / total * self.nays as u128 + balance % total * self.nays as u128 / total;
+        (aye, nay, balance - aye - nay)
+    }
+
+    /// Percentages of aye, nay and abstain, for display only.
+    pub fn percentages(&self) -> (f32, f32, f32) {
+        let total = self.total().max(1) as f32;
+        (
+            self.ayes as f32 * 100.0 / total,
+            self.nays as f32 * 100.0 / total,
+            self.abstains as f32 * 100.0 / total,
+        )
+    }
+}
+
+/// Vote as submitted on chain.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum OnChainVote {
+    Aye,
+    Nay,
+    Abstain,
+    Split(VoteSplit),
+}
+
+impl From<Option<bool>> for OnChainVote {
+    fn from(vote: Option<bool>) -> Self {
+        match vote {
+            Some(true) => Self::Aye,
+            Some(false) => Self::Nay,
+            None => Self::Abstain,
+        }
+    }
+}
+
+impl Display for OnChainVote {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Aye => write!(f, "AYE"),
+            Self::Nay => write!(f, "NAY"),
+            Self::Abstain => write!(f, "ABSTAIN"),
+            Self::Split(split) => {
+                let (aye, nay, abstain) = split.percentages();
+                write!(
+                    f,
+                    "SPLIT {aye:.1}% AYE / {nay:.1}% NAY / {abstain:.1}% ABSTAIN"
+                )
+            }
+        }
+    }
+}
+
+/// Vote cast by one of the DAO's voting accounts within a vote batch.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountVote {
+    pub address: String,
+    pub vote: OnChainVote,
+    pub balance: u128,
+    /// Balance delegated to the account in the referendum's class, which the chain adds to an
+    /// aye or nay vote. Only read when voting with full voting power.
+    #[serde(default)]
+    pub delegated_balance: u128,
+}
+
+/// A single account's vote as stored in `ConvictionVoting::VotingFor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvictionVote {
+    Standard {
+        aye: bool,
+        conviction: u8,
+        balance: u128,
+    },
+    Split {
+        aye: u128,
+        nay: u128,
+    },
+    SplitAbstain {
+        aye: u128,
+        nay: u128,
+        abstain: u128,
+    },
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl ConvictionVote {
+    /// The vote that gets submitted for an account voting `vote` with `balance`.
+    pub fn new(vote: OnChainVote, balance: u128, conviction: u8) -> Self {
+        match vote {
+            OnChainVote::Aye | OnChainVote::Nay => Self::Standard {
+                aye: vote == OnChainVote::Aye,
+                conviction,
+                balance,
+            },
+            OnChainVote::Abstain => Self::SplitAbstain {
+                aye: 0,
+                nay: 0,
+                abstain: balance,
+            },
+            OnChainVote::Split(split) => {
+                let (aye, nay, abstain) = split.split_balance(balance);
+                if abstain == 0 {
+                    Self::Split { aye, nay }
+                } else {
+                    Self::SplitAbstain { aye, nay, abstain }
+                }
+            }
+        }
+    }
+
+    /// The inverse of [`ConvictionVote::new`], returns the vote, balance and conviction.
+    /// Split shares are reduced by their greatest common divisor so that splitting the balance
+    /// again gives back the same amounts, unless they don't fit the shares.
+    pub fn to_on_chain_vote(&self) -> (OnChainVote, u128, u8) {
+        let (aye, nay, abstain) = match *self {
+            Self::Standard {
+                aye,
+                conviction,
+                balance,
+            } => return (OnChainVote::from(Some(aye)), balance, conviction),
+            Self::SplitAbstain {
+                aye: 0,
+                nay: 0,
+                abstain,
+            } => return (OnChainVote::Abstain, abstain, 0),
+            Self::Split { aye, nay } => (aye, nay, 0),
+            Self::SplitAbstain { aye, nay, abstain } => (aye, nay, abstain),
+        };
+        let balance = aye + nay + abstain;
+        let divisor = gcd(gcd(aye, nay), abstain).max(1);
+        let shares = (aye / divisor, nay / divisor, abstain / divisor);
+        let split = match (
+            u32::try_from(shares.0),
+            u32::try_from(shares.1),
+            u32::try_from(shares.2),
+        ) {
+            (Ok(ayes), Ok(nays), Ok(abstains)) => VoteSplit {
+                ayes,
+                nays,
+                abstains,
+            },
+            _ => {
+                let total = balance.max(1);
+                let ayes = (aye * 10_000 / total) as u32;
+                let nays = if abstain == 0 {
+                    10_000 - ayes
+                } else {
+                    (nay * 10_000 / total) as u32
+                };
+                VoteSplit {
+                    ayes,
+                    nays,
+                    abstains: 10_000 - ayes - nays,
+                }
+            }
+        };
+        (OnChainVote::Split(split), balance, 0)
+    }
+
+    fn split_amounts(&self) -> Option<(u128, u128, u128)> {
+        match *self {
+            Self::Standard { .. } => None,
+            Self::Split { aye, nay } => Some((aye, nay, 0)),
+            Self::SplitAbstain { aye, nay, abstain } => Some((aye, nay, abstain)),
+        }
+    }
+
+    /// Whether the votes are the same, allowing split amounts to differ by one basis point of
+    /// the balance, which is as precise as [`ConvictionVote::to_on_chain_vote`] may be.
+    pub fn matches(&self, other: &Self) -> bool {
+        match (self.split_amounts(), other.split_amounts()) {
+            (None, None) => self == other,
+            (Some(amounts), Some(other_amounts)) => {
+                let balance = (amounts.0 + amounts.1 + amounts.2)
+                    .max(other_amounts.0 + other_amounts.1 + other_amounts.2);
+                let tolerance = balance / 10_000;
+                amounts.0.abs_diff(other_amounts.0) <= tolerance
+                    && amounts.1.abs_diff(other_amounts.1) <= tolerance
+                    && amounts.2.abs_diff(other_amounts.2) <= tolerance
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -158,6 +376,39 @@ pub struct Vote {
     pub subsquare_comment_index: Option<u32>,
     pub has_coi: bool,
     pub is_forced: bool,
+    pub policy_record: Option<VotePolicyRecord>,
+    pub split: Option<VoteSplit>,
+    pub account_votes: Vec<AccountVote>,
+    pub is_cleaned_up: bool,
+}
+
+impl Vote {
+    pub fn on_chain_vote(&self) -> OnChainVote {
+        match self.split {
+            Some(split) => OnChainVote::Split(split),
+            None => OnChainVote::from(self.vote),
+        }
+    }
+}
+
+/// Policy inputs and rendered evaluation recorded with an on-chain vote.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VotePolicyRecord {
+    pub policy_version: String,
+    pub policy: Policy,
+    pub vote_counts: VoteCounts,
+    /// Blocks left in the decision or confirmation period at the time of the vote.
+    #[serde(default)]
+    pub blocks_left: Option<u64>,
+    pub description_lines: Vec<String>,
+}
+
+impl VotePolicyRecord {
+    /// Re-runs the evaluation under the policy that was active at the time of the vote.
+    pub fn reevaluate(&self) -> (PolicyEvaluation, Vec<String>) {
+        self.policy.evaluate(&self.vote_counts, self.blocks_left)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -186,3 +437,59 @@ pub struct PendingMemberVote {
     pub vote: Option<bool>,
     pub feedback: String,
 }
+
+/// Member vote to record along with the on-chain vote.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoteIntentMemberVote {
+    pub cid: String,
+    pub address: String,
+    pub vote: Option<bool>,
+    pub feedback: String,
+}
+
+/// Everything needed to record a vote once its extrinsic is finalized.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoteIntentRecord {
+    pub vote: Option<bool>,
+    pub split: Option<VoteSplit>,
+    pub balance: u128,
+    pub conviction: u8,
+    pub has_coi: bool,
+    pub is_forced: bool,
+    /// Whether the members' feedback summary gets posted as a SubSquare comment.
+    #[serde(default)]
+    pub post_feedback: bool,
+    pub policy_record: Option<VotePolicyRecord>,
+    pub account_votes: Vec<AccountVote>,
+    pub member_votes: Vec<VoteIntentMemberVote>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar")]
+pub enum VoteIntentStatus {
+    /// Saved before the submission, the outcome is unknown until it gets submitted or fails.
+    Pending,
+    /// Finalized on chain but not recorded yet.
+    Submitted,
+    Recorded,
+    Failed,
+    /// Left pending by a restart, and not found on chain or not checked against it.
+    Abandoned,
+}
+
+/// A vote saved before its extrinsic gets submitted, so that a restart can finish recording it.
+#[derive(Clone, Debug)]
+pub struct VoteIntent {
+    pub id: u32,
+    pub network_id: u32,
+    pub referendum_id: u32,
+    pub index: u32,
+    pub status: VoteIntentStatus,
+    pub record: VoteIntentRecord,
+    pub block_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub extrinsic_index: Option<u32>,
+    pub vote_id: Option<u32>,
+}
@@ -1,15 +1,101 @@
 use crate::governance::track::Track;
+use crate::governance::{OnChainVote, VoteSplit};
+use crate::substrate::balance::Balance;
+use serde::{Deserialize, Serialize};
 
-pub fn round_half_down(x: f32) -> u32 {
-    (x - 0.5).ceil() as u32
+const BASIS_POINTS_PER_UNIT: u64 = 10_000;
+
+/// A percentage held in basis points (hundredths of a percent), so that thresholds
+/// are checked with integer arithmetic. Policy files still express it as a decimal
+/// percentage such as `37.5`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Percentage(u32);
+
+impl Percentage {
+    pub fn from_basis_points(basis_points: u32) -> anyhow::Result<Self> {
+        if basis_points as u64 > BASIS_POINTS_PER_UNIT {
+            anyhow::bail!("Invalid threshold basis points: {basis_points}");
+        }
+        Ok(Self(basis_points))
+    }
+
+    pub fn basis_points(&self) -> u32 {
+        self.0
+    }
+
+    /// Display value only, never used for evaluation.
+    pub fn as_f32(&self) -> f32 {
+        self.0 as f32 / 100.0
+    }
+
+    /// Display value of this percentage of the given count.
+    pub fn of(&self, count: u32) -> f32 {
+        (self.0 as u64 * count as u64) as f32 / BASIS_POINTS_PER_UNIT as f32
+    }
+
+    /// This percentage of the given amount, rounded down.
+    pub fn apply(&self, amount: u128) -> u128 {
+        let basis_points = self.0 as u128;
+        let per_unit = BASIS_POINTS_PER_UNIT as u128;
+        amount / per_unit * basis_points + amount % per_unit * basis_points / per_unit
+    }
+
+    /// Compares `part / whole` against this percentage exactly by cross-multiplying.
+    fn compare(&self, part: u32, whole: u32) -> std::cmp::Ordering {
+        (part as u64 * BASIS_POINTS_PER_UNIT).cmp(&(self.0 as u64 * whole as u64))
+    }
+}
+
+impl TryFrom<f64> for Percentage {
+    type Error = String;
+
+    fn try_from(percent: f64) -> Result<Self, Self::Error> {
+        if !percent.is_finite() || !(0.0..=100.0).contains(&percent) {
+            return Err(format!("Invalid threshold percentage: {percent}"));
+        }
+        let basis_points = (percent * 100.0).round();
+        if (percent * 100.0 - basis_points).abs() > 1e-6 {
+            return Err(format!(
+                "Threshold percentage {percent} has more than two decimal places."
+            ));
+        }
+        Ok(Self(basis_points as u32))
+    }
+}
+
+impl From<Percentage> for f64 {
+    fn from(percentage: Percentage) -> Self {
+        percentage.0 as f64 / 100.0
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// Raw tallies of the members of a single membership type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct MembershipVoteCounts {
+    pub members: u32,
+    pub ayes: u32,
+    pub nays: u32,
+    pub abstains: u32,
+}
+
+impl MembershipVoteCounts {
+    pub fn participation(&self) -> u32 {
+        self.ayes
+            .saturating_add(self.nays)
+            .saturating_add(self.abstains)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 pub struct VoteCounts {
     members: u32,
     ayes: u32,
     nays: u32,
     abstains: u32,
+    /// Core member share of the counts above, the rest are community members.
+    #[serde(default)]
+    core: MembershipVoteCounts,
 }
 
 impl VoteCounts {
@@ -19,6 +105,51 @@ impl VoteCounts {
             ayes,
             nays,
             abstains,
+            core: MembershipVoteCounts::default(),
+        }
+    }
+
+    pub fn from_membership_counts(
+        core: MembershipVoteCounts,
+        community: MembershipVoteCounts,
+    ) -> Self {
+        Self {
+            members: core.members.saturating_add(community.members),
+            ayes: core.ayes.saturating_add(community.ayes),
+            nays: core.nays.saturating_add(community.nays),
+            abstains: core.abstains.saturating_add(community.abstains),
+            core,
+        }
+    }
+
+    pub fn core(&self) -> MembershipVoteCounts {
+        self.core
+    }
+
+    pub fn community(&self) -> MembershipVoteCounts {
+        MembershipVoteCounts {
+            members: self.members.saturating_sub(self.core.members),
+            ayes: self.ayes.saturating_sub(self.core.ayes),
+            nays: self.nays.saturating_sub(self.core.nays),
+            abstains: self.abstains.saturating_sub(self.core.abstains),
+        }
+    }
+
+    /// Counts with every core and community member multiplied by their weight.
+    pub fn weighted(&self, weights: &MemberWeights) -> Self {
+        let core = self.core;
+        let community = self.community();
+        let weigh = |core_count: u32, community_count: u32| {
+            core_count
+                .saturating_mul(weights.core)
+                .saturating_add(community_count.saturating_mul(weights.community))
+        };
+        Self {
+            members: weigh(core.members, community.members),
+            ayes: weigh(core.ayes, community.ayes),
+            nays: weigh(core.nays, community.nays),
+            abstains: weigh(core.abstains, community.abstains),
+            core,
         }
     }
 
@@ -29,13 +160,62 @@ impl VoteCounts {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MemberWeights {
+    pub core: u32,
+    pub community: u32,
+}
+
+impl Default for MemberWeights {
+    fn default() -> Self {
+        Self {
+            core: 1,
+            community: 1,
+        }
+    }
+}
+
+impl MemberWeights {
+    pub fn is_uniform(&self) -> bool {
+        self.core == self.community
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum ParticipationRequirement {
     AbstainBeforePercent(Comparison),
     NoVoteBeforePercent(Comparison),
 }
 
-#[derive(Clone, Copy, Debug)]
+impl ParticipationRequirement {
+    pub fn comparison(&self) -> Comparison {
+        match self {
+            ParticipationRequirement::AbstainBeforePercent(comparison) => *comparison,
+            ParticipationRequirement::NoVoteBeforePercent(comparison) => *comparison,
+        }
+    }
+
+    fn with_threshold(&self, threshold: Percentage) -> Self {
+        match self {
+            ParticipationRequirement::AbstainBeforePercent(comparison) => {
+                ParticipationRequirement::AbstainBeforePercent(comparison.with_threshold(threshold))
+            }
+            ParticipationRequirement::NoVoteBeforePercent(comparison) => {
+                ParticipationRequirement::NoVoteBeforePercent(comparison.with_threshold(threshold))
+            }
+        }
+    }
+}
+
+/// Lower participation threshold that applies once at most `blocks_left` blocks remain in
+/// the decision or confirmation period.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ParticipationRelaxation {
+    pub blocks_left: u64,
+    pub threshold: Percentage,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum MajorityNominator {
     Ayes,
     Nays,
@@ -52,7 +232,7 @@ impl MajorityNominator {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum MajorityDenominator {
     OfNonAbstainVotes,
     OfAllVotes,
@@ -72,27 +252,35 @@ impl MajorityDenominator {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum Comparison {
-    GreaterThan(f32),
-    GreaterThanOrEqual(f32),
+    GreaterThan(Percentage),
+    GreaterThanOrEqual(Percentage),
 }
 
 impl Comparison {
-    pub fn holds(&self, value: f32) -> bool {
+    /// Whether `part / whole` satisfies the comparison. Never holds for an empty whole.
+    pub fn holds(&self, part: u32, whole: u32) -> bool {
+        if whole == 0 {
+            return false;
+        }
         match self {
-            Comparison::GreaterThan(threshold) => value > *threshold,
-            Comparison::GreaterThanOrEqual(threshold) => value >= *threshold,
+            Comparison::GreaterThan(threshold) => threshold.compare(part, whole).is_gt(),
+            Comparison::GreaterThanOrEqual(threshold) => threshold.compare(part, whole).is_ge(),
         }
     }
 
-    pub fn threshold_rate(&self) -> f32 {
+    pub fn threshold(&self) -> Percentage {
         match self {
             Comparison::GreaterThan(threshold) => *threshold,
             Comparison::GreaterThanOrEqual(threshold) => *threshold,
         }
     }
 
+    pub fn threshold_rate(&self) -> f32 {
+        self.threshold().as_f32()
+    }
+
     pub fn symbol(&self) -> String {
         match self {
             Comparison::GreaterThan(_) => ">".to_string(),
@@ -106,94 +294,361 @@ impl Comparison {
             Comparison::GreaterThanOrEqual(_) => "<".to_string(),
         }
     }
+
+    fn with_threshold(&self, threshold: Percentage) -> Self {
+        match self {
+            Comparison::GreaterThan(_) => Comparison::GreaterThan(threshold),
+            Comparison::GreaterThanOrEqual(_) => Comparison::GreaterThanOrEqual(threshold),
+        }
+    }
+}
+
+/// How the outcome is voted on chain once the participation requirements are met.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum VoteMode {
+    /// Whole balance on the side chosen by the majority rules.
+    #[default]
+    Majority,
+    /// Balance split in proportion to the weighted aye, nay and abstain votes.
+    Proportional,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// Balance the DAO votes with.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum VoteBalance {
+    /// Fixed number of whole tokens.
+    FixedTokens(u64),
+    /// Percentage of the voting account's free balance.
+    PercentOfFreeBalance(Percentage),
+    /// All the voting power of the account: its whole balance, free and reserved, and the balance
+    /// delegated to it in the track's class. Conviction voting adds the delegations to the
+    /// account's aye or nay vote on chain, so only the account's own balance gets submitted.
+    FullVotingPower,
+}
+
+impl Default for VoteBalance {
+    fn default() -> Self {
+        Self::FixedTokens(1)
+    }
+}
+
+impl VoteBalance {
+    /// Resolves the vote balance for an account with the given balance, failing when the
+    /// account cannot cover it.
+    pub fn resolve(&self, token_decimals: u32, account_balance: &Balance) -> anyhow::Result<u128> {
+        let total_balance = account_balance
+            .free
+            .saturating_add(account_balance.reserved);
+        let balance = match self {
+            VoteBalance::FixedTokens(tokens) => (*tokens as u128)
+                .checked_mul(10u128.pow(token_decimals))
+                .ok_or(anyhow::anyhow!(
+                    "Vote balance of {tokens} tokens overflows."
+                ))?,
+            VoteBalance::PercentOfFreeBalance(percentage) => percentage.apply(account_balance.free),
+            VoteBalance::FullVotingPower => total_balance,
+        };
+        if balance == 0 {
+            anyhow::bail!("Vote balance is zero.");
+        }
+        if balance > total_balance {
+            anyhow::bail!("Vote balance {balance} exceeds the account balance {total_balance}.");
+        }
+        Ok(balance)
+    }
+}
+
+fn default_conviction() -> u8 {
+    1
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Policy {
     track: Track,
     participation_requirement: ParticipationRequirement,
     majority_nominator: MajorityNominator,
     majority_comparison: Comparison,
     majority_denominator: MajorityDenominator,
+    #[serde(default)]
+    minimum_vote_count: Option<u32>,
+    #[serde(default)]
+    member_weights: MemberWeights,
+    /// Core member participation required before voting at all.
+    #[serde(default)]
+    core_quorum: Option<Comparison>,
+    /// Core member ayes of core non-abstain votes required for an aye.
+    #[serde(default)]
+    core_majority: Option<Comparison>,
+    #[serde(default)]
+    participation_relaxations: Vec<ParticipationRelaxation>,
+    #[serde(default)]
+    vote_mode: VoteMode,
+    #[serde(default)]
+    vote_balance: VoteBalance,
+    #[serde(default = "default_conviction")]
+    conviction: u8,
 }
 
-impl Policy {
-    pub fn policy_for_track(track: &Track) -> Self {
-        match track {
-            Track::Root
-            | Track::WhitelistedCaller
-            | Track::WishForChange
-            | Track::Treasurer
-            | Track::FellowshipAdmin
-            | Track::StakingAdmin
-            | Track::LeaseAdmin
-            | Track::GeneralAdmin
-            | Track::AuctionAdmin
-            | Track::ReferendumCanceller
-            | Track::ReferendumKiller
-            | Track::BigSpender => Self {
-                track: *track,
-                participation_requirement: ParticipationRequirement::NoVoteBeforePercent(
-                    Comparison::GreaterThanOrEqual(50.0),
-                ),
-                majority_nominator: MajorityNominator::Ayes,
-                majority_comparison: Comparison::GreaterThanOrEqual(60.0),
-                majority_denominator: MajorityDenominator::OfAllVotes,
-            },
-            Track::MediumSpender => Self {
-                track: *track,
-                participation_requirement: ParticipationRequirement::NoVoteBeforePercent(
-                    Comparison::GreaterThanOrEqual(50.0),
-                ),
-                majority_nominator: MajorityNominator::Ayes,
-                majority_comparison: Comparison::GreaterThan(50.0),
-                majority_denominator: MajorityDenominator::OfAllVotes,
-            },
-            Track::SmallSpender | Track::BigTipper => Self {
-                track: *track,
-                participation_requirement: ParticipationRequirement::AbstainBeforePercent(
-                    Comparison::GreaterThanOrEqual(37.5),
-                ),
-                majority_nominator: MajorityNominator::Ayes,
-                majority_comparison: Comparison::GreaterThan(50.0),
-                majority_denominator: MajorityDenominator::OfNonAbstainVotes,
-            },
-            Track::SmallTipper => Self {
-                track: *track,
-                participation_requirement: ParticipationRequirement::AbstainBeforePercent(
-                    Comparison::GreaterThanOrEqual(25.0),
-                ),
-                majority_nominator: MajorityNominator::Ayes,
-                majority_comparison: Comparison::GreaterThan(50.0),
-                majority_denominator: MajorityDenominator::OfNonAbstainVotes,
-            },
+#[derive(Clone, Debug, Deserialize)]
+struct TrackPolicyDefinition {
+    tracks: Vec<Track>,
+    participation_requirement: ParticipationRequirement,
+    majority_nominator: MajorityNominator,
+    majority_comparison: Comparison,
+    majority_denominator: MajorityDenominator,
+    #[serde(default)]
+    minimum_vote_count: Option<u32>,
+    #[serde(default)]
+    member_weights: MemberWeights,
+    #[serde(default)]
+    core_quorum: Option<Comparison>,
+    #[serde(default)]
+    core_majority: Option<Comparison>,
+    #[serde(default)]
+    participation_relaxations: Vec<ParticipationRelaxation>,
+    #[serde(default)]
+    vote_mode: VoteMode,
+    #[serde(default)]
+    vote_balance: VoteBalance,
+    #[serde(default = "default_conviction")]
+    conviction: u8,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct VotingPolicyDefinition {
+    version: String,
+    track_policies: Vec<TrackPolicyDefinition>,
+}
+
+/// Set of per-track policies ratified under a single voting policy version.
+#[derive(Clone, Debug)]
+pub struct VotingPolicy {
+    version: String,
+    policies: Vec<Policy>,
+}
+
+impl VotingPolicy {
+    /// Loads and validates the policy file at the given path (extension optional),
+    /// which should declare the given version.
+    pub fn load(file_path: &str, version: &str) -> anyhow::Result<Self> {
+        let definition: VotingPolicyDefinition = config::Config::builder()
+            .add_source(config::File::with_name(file_path))
+            .build()?
+            .try_deserialize()?;
+        Self::from_definition(definition, version)
+    }
+
+    pub fn from_toml_str(toml: &str, version: &str) -> anyhow::Result<Self> {
+        let definition: VotingPolicyDefinition = config::Config::builder()
+            .add_source(config::File::from_str(toml, config::FileFormat::Toml))
+            .build()?
+            .try_deserialize()?;
+        Self::from_definition(definition, version)
+    }
+
+    fn from_definition(definition: VotingPolicyDefinition, version: &str) -> anyhow::Result<Self> {
+        if definition.version != version {
+            anyhow::bail!(
+                "Voting policy version mismatch. Expected {version}, found {}.",
+                definition.version,
+            );
+        }
+        let mut policies: Vec<Policy> = Vec::new();
+        for track_policy in definition.track_policies.iter() {
+            if track_policy.minimum_vote_count == Some(0) {
+                anyhow::bail!("Minimum vote count should be greater than zero.");
+            }
+            if track_policy.member_weights.core == 0 && track_policy.member_weights.community == 0 {
+                anyhow::bail!("At least one membership type should have a non-zero weight.");
+            }
+            if track_policy.conviction > 6 {
+                anyhow::bail!("Invalid conviction: {}", track_policy.conviction);
+            }
+            if track_policy.vote_balance == VoteBalance::FixedTokens(0) {
+                anyhow::bail!("Fixed vote balance should be greater than zero.");
+            }
+            if track_policy.vote_balance == VoteBalance::FullVotingPower
+                && track_policy.vote_mode == VoteMode::Proportional
+            {
+                anyhow::bail!("Full voting power needs aye or nay votes, split votes don't carry delegations.");
+            }
+            // percentages above 100% are already rejected by `Percentage`
+            if let VoteBalance::PercentOfFreeBalance(percentage) = track_policy.vote_balance {
+                if percentage.basis_points() == 0 {
+                    anyhow::bail!("Vote balance percentage should be greater than zero.");
+                }
+            }
+            let participation_threshold = track_policy
+                .participation_requirement
+                .comparison()
+                .threshold();
+            for relaxation in track_policy.participation_relaxations.iter() {
+                if relaxation.threshold.basis_points() >= participation_threshold.basis_points() {
+                    anyhow::bail!(
+                        "Relaxed participation threshold {:.1}% should be lower than {:.1}%.",
+                        relaxation.threshold.as_f32(),
+                        participation_threshold.as_f32(),
+                    );
+                }
+            }
+            for track in track_policy.tracks.iter() {
+                if policies.iter().any(|policy| policy.track == *track) {
+                    anyhow::bail!(
+                        "Voting policy {version} defines {} more than once.",
+                        track.name(),
+                    );
+                }
+                policies.push(Policy {
+                    track: *track,
+                    participation_requirement: track_policy.participation_requirement,
+                    majority_nominator: track_policy.majority_nominator,
+                    majority_comparison: track_policy.majority_comparison,
+                    majority_denominator: track_policy.majority_denominator,
+                    minimum_vote_count: track_policy.minimum_vote_count,
+                    member_weights: track_policy.member_weights,
+                    core_quorum: track_policy.core_quorum,
+                    core_majority: track_policy.core_majority,
+                    participation_relaxations: track_policy.participation_relaxations.clone(),
+                    vote_mode: track_policy.vote_mode,
+                    vote_balance: track_policy.vote_balance,
+                    conviction: track_policy.conviction,
+                });
+            }
+        }
+        for track in enum_iterator::all::<Track>() {
+            if !policies.iter().any(|policy| policy.track == track) {
+                anyhow::bail!("Voting policy {version} does not define {}.", track.name(),);
+            }
         }
+        Ok(Self {
+            version: definition.version,
+            policies,
+        })
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn policy_for_track(&self, track: &Track) -> Policy {
+        // all tracks are guaranteed to be defined by the validation in `from_definition`
+        self.policies
+            .iter()
+            .find(|policy| policy.track == *track)
+            .expect("Policy not defined for track.")
+            .clone()
+    }
+}
+
+impl Policy {
+    pub fn vote_balance(&self) -> VoteBalance {
+        self.vote_balance
     }
 
-    pub fn evaluate(&self, vote_counts: &VoteCounts) -> (PolicyEvaluation, Vec<String>) {
-        let participation_percent =
-            (vote_counts.participation() as f32) * 100.0 / (vote_counts.members as f32);
-        let simple_majority_threshold = 50.0 * (vote_counts.participation() as f32) / 100.0;
-        let majority_nominator = self.majority_nominator.get(vote_counts) as f32;
-        let majority_denominator = self.majority_denominator.get(vote_counts) as f32;
-        let non_aye_percent = (vote_counts.ayes + vote_counts.abstains) as f32 * 100.0
-            / vote_counts.participation() as f32;
-        let majority_percent = majority_nominator * 100.0 / majority_denominator;
-        let majority_threshold =
-            self.majority_comparison.threshold_rate() * majority_denominator / 100.0;
+    pub fn conviction(&self) -> u8 {
+        self.conviction
+    }
+
+    /// Participation requirement in effect with the given number of blocks left in the
+    /// decision or confirmation period, the lowest threshold among the due relaxations.
+    pub fn effective_participation_requirement(
+        &self,
+        blocks_left: Option<u64>,
+    ) -> ParticipationRequirement {
+        let Some(blocks_left) = blocks_left else {
+            return self.participation_requirement;
+        };
+        self.participation_relaxations
+            .iter()
+            .filter(|relaxation| blocks_left <= relaxation.blocks_left)
+            .map(|relaxation| relaxation.threshold)
+            .min_by_key(|threshold| threshold.basis_points())
+            .map(|threshold| self.participation_requirement.with_threshold(threshold))
+            .unwrap_or(self.participation_requirement)
+    }
+
+    pub fn evaluate(
+        &self,
+        vote_counts: &VoteCounts,
+        blocks_left: Option<u64>,
+    ) -> (PolicyEvaluation, Vec<String>) {
+        let weighted = vote_counts.weighted(&self.member_weights);
+        let participation = weighted.participation();
+        let majority_nominator = self.majority_nominator.get(&weighted);
+        let majority_denominator = self.majority_denominator.get(&weighted);
+        let non_ayes = weighted.ayes.saturating_add(weighted.abstains);
+        let majority_threshold = self
+            .majority_comparison
+            .threshold()
+            .of(majority_denominator);
         let mut description_lines = Vec::new();
 
         description_lines.push("```".to_string());
         description_lines.push(self.track.name().to_uppercase());
         description_lines.push("-".repeat(self.track.name().len()));
-        description_lines.push(format!("{} available members", vote_counts.members));
-        description_lines.push(format!(
-            "🟢 {} • 🔴 {} • ⚪️ {}",
-            vote_counts.ayes, vote_counts.nays, vote_counts.abstains
-        ));
-        match &self.participation_requirement {
+        if self.member_weights.is_uniform() {
+            description_lines.push(format!("{} available members", vote_counts.members));
+            description_lines.push(format!(
+                "🟢 {} • 🔴 {} • ⚪️ {}",
+                vote_counts.ayes, vote_counts.nays, vote_counts.abstains
+            ));
+        } else {
+            description_lines.push(format!(
+                "Weighted (raw) • core ×{} • community ×{}",
+                self.member_weights.core, self.member_weights.community,
+            ));
+            description_lines.push(format!(
+                "{} ({}) available members",
+                weighted.members, vote_counts.members,
+            ));
+            description_lines.push(format!(
+                "🟢 {} ({}) • 🔴 {} ({}) • ⚪️ {} ({})",
+                weighted.ayes,
+                vote_counts.ayes,
+                weighted.nays,
+                vote_counts.nays,
+                weighted.abstains,
+                vote_counts.abstains,
+            ));
+        }
+        let core = vote_counts.core();
+        if self.core_quorum.is_some() || self.core_majority.is_some() {
+            description_lines.push(format!(
+                "Core: 🟢 {} • 🔴 {} • ⚪️ {} of {}",
+                core.ayes, core.nays, core.abstains, core.members,
+            ));
+        }
+        if let Some(minimum_vote_count) = self.minimum_vote_count {
+            if vote_counts.participation() < minimum_vote_count {
+                description_lines.push(format!("▶ No vote before {minimum_vote_count} votes"));
+                description_lines.push("➖ NO VOTE".to_string());
+                description_lines.push("```".to_string());
+                return (
+                    PolicyEvaluation::MinimumVoteCountNotMet {
+                        vote_counts: *vote_counts,
+                        minimum_vote_count,
+                    },
+                    description_lines,
+                );
+            }
+        }
+        let participation_requirement = self.effective_participation_requirement(blocks_left);
+        if let Some(blocks_left) = blocks_left {
+            let base_comparison = self.participation_requirement.comparison();
+            let comparison = participation_requirement.comparison();
+            if comparison.threshold() != base_comparison.threshold() {
+                description_lines.push(format!(
+                    "⏳ {blocks_left} blocks left, participation {}{:.1}% → {}{:.1}%",
+                    base_comparison.symbol(),
+                    base_comparison.threshold_rate(),
+                    comparison.symbol(),
+                    comparison.threshold_rate(),
+                ));
+            }
+        }
+        match &participation_requirement {
             ParticipationRequirement::AbstainBeforePercent(comparison) => {
-                if !comparison.holds(participation_percent) {
+                if !comparison.holds(participation, weighted.members) {
                     description_lines.push(format!(
                         "▶️ Abstain before {}{:.1}% participation",
                         comparison.symbol(),
@@ -204,9 +659,7 @@ impl Policy {
                     return (
                         PolicyEvaluation::AbstainThresholdNotMet {
                             vote_counts: *vote_counts,
-                            abstain_threshold: (vote_counts.members as f32)
-                                * comparison.threshold_rate()
-                                / 100.0,
+                            abstain_threshold: comparison.threshold().of(weighted.members),
                         },
                         description_lines,
                     );
@@ -219,7 +672,7 @@ impl Policy {
                 }
             }
             ParticipationRequirement::NoVoteBeforePercent(comparison) => {
-                if !comparison.holds(participation_percent) {
+                if !comparison.holds(participation, weighted.members) {
                     description_lines.push(format!(
                         "▶ No vote before {}{:.1}% participation",
                         comparison.symbol(),
@@ -230,9 +683,7 @@ impl Policy {
                     return (
                         PolicyEvaluation::ParticipationNotMet {
                             vote_counts: *vote_counts,
-                            participation_threshold: (vote_counts.members as f32)
-                                * comparison.threshold_rate()
-                                / 100.0,
+                            participation_threshold: comparison.threshold().of(weighted.members),
                         },
                         description_lines,
                     );
@@ -246,20 +697,66 @@ impl Policy {
             }
         }
 
-        if (vote_counts.abstains as f32) > simple_majority_threshold {
+        if let Some(core_quorum) = &self.core_quorum {
+            if !core_quorum.holds(core.participation(), core.members) {
+                description_lines.push(format!(
+                    "▶ No vote before {}{:.1}% core participation",
+                    core_quorum.symbol(),
+                    core_quorum.threshold_rate(),
+                ));
+                description_lines.push("➖ NO VOTE".to_string());
+                description_lines.push("```".to_string());
+                return (
+                    PolicyEvaluation::CoreQuorumNotMet {
+                        vote_counts: *vote_counts,
+                        core_quorum_threshold: core_quorum.threshold().of(core.members),
+                    },
+                    description_lines,
+                );
+            } else {
+                description_lines.push(format!(
+                    "✓ {}{:.1}% required core participation met",
+                    core_quorum.symbol(),
+                    core_quorum.threshold_rate(),
+                ));
+            }
+        }
+
+        if self.vote_mode == VoteMode::Proportional {
+            let split = VoteSplit {
+                ayes: weighted.ayes,
+                nays: weighted.nays,
+                abstains: weighted.abstains,
+            };
+            let (aye_percent, nay_percent, abstain_percent) = split.percentages();
+            description_lines.push(format!(
+                "▶ Proportional vote 🟢 {aye_percent:.1}% • 🔴 {nay_percent:.1}% • ⚪️ {abstain_percent:.1}%"
+            ));
+            description_lines.push("⚖️ SPLIT".to_string());
+            description_lines.push("```".to_string());
+            return (
+                PolicyEvaluation::Split {
+                    vote_counts: *vote_counts,
+                    split,
+                },
+                description_lines,
+            );
+        }
+
+        if (weighted.abstains as u64) * 2 > participation as u64 {
             description_lines.push("▶ Majority of all votes is abstain".to_string());
             description_lines.push("⚪ ABSTAIN".to_string());
             description_lines.push("```".to_string());
             return (
                 PolicyEvaluation::MajorityAbstain {
                     vote_counts: *vote_counts,
-                    majority_threshold: simple_majority_threshold,
+                    majority_threshold: participation as f32 / 2.0,
                 },
                 description_lines,
             );
         }
 
-        if vote_counts.abstains == 0 && (vote_counts.ayes == vote_counts.nays) {
+        if weighted.abstains == 0 && (weighted.ayes == weighted.nays) {
             description_lines.push("▶ Ayes equal nays with no abstains".to_string());
             description_lines.push("⚪ ABSTAIN".to_string());
             description_lines.push("```".to_string());
@@ -271,7 +768,16 @@ impl Policy {
             );
         }
 
-        if self.majority_comparison.holds(majority_percent) {
+        let core_majority_met = match &self.core_majority {
+            Some(core_majority) => {
+                core_majority.holds(core.ayes, core.ayes.saturating_add(core.nays))
+            }
+            None => true,
+        };
+        let majority_met = self
+            .majority_comparison
+            .holds(majority_nominator, majority_denominator);
+        if majority_met && core_majority_met {
             description_lines.push(format!(
                 "▶ Ayes {}{:.1}% of {} votes",
                 self.majority_comparison.symbol(),
@@ -292,17 +798,31 @@ impl Policy {
             );
         } else {
             description_lines.push(format!(
-                "✘ Ayes {}{:.1}% of {} votes",
-                self.majority_comparison.negative_symbol(),
+                "{} Ayes {}{:.1}% of {} votes",
+                if majority_met { "✓" } else { "✘" },
+                if majority_met {
+                    self.majority_comparison.symbol()
+                } else {
+                    self.majority_comparison.negative_symbol()
+                },
                 self.majority_comparison.threshold_rate(),
                 match self.majority_denominator {
                     MajorityDenominator::OfNonAbstainVotes => "non-abstain",
                     MajorityDenominator::OfAllVotes => "all",
                 },
             ));
+            if let Some(core_majority) = &self.core_majority {
+                if !core_majority_met {
+                    description_lines.push(format!(
+                        "✘ Core ayes {}{:.1}% of core non-abstain votes",
+                        core_majority.negative_symbol(),
+                        core_majority.threshold_rate(),
+                    ));
+                }
+            }
         }
 
-        if self.majority_comparison.holds(non_aye_percent) {
+        if self.majority_comparison.holds(non_ayes, participation) {
             description_lines.push(format!(
                 "▶ Ayes and abstains are {}{:.1}% of all votes",
                 self.majority_comparison.symbol(),
@@ -313,9 +833,7 @@ impl Policy {
             return (
                 PolicyEvaluation::AyeAbstainMajorityAbstain {
                     vote_counts: *vote_counts,
-                    majority_threshold: self.majority_comparison.threshold_rate()
-                        * vote_counts.participation() as f32
-                        / 100.0,
+                    majority_threshold: self.majority_comparison.threshold().of(participation),
                 },
                 description_lines,
             );
@@ -335,10 +853,18 @@ impl Policy {
 
 #[derive(PartialEq, Copy, Clone, Debug)]
 pub enum PolicyEvaluation {
+    MinimumVoteCountNotMet {
+        vote_counts: VoteCounts,
+        minimum_vote_count: u32,
+    },
     AbstainThresholdNotMet {
         vote_counts: VoteCounts,
         abstain_threshold: f32,
     },
+    CoreQuorumNotMet {
+        vote_counts: VoteCounts,
+        core_quorum_threshold: f32,
+    },
     ParticipationNotMet {
         vote_counts: VoteCounts,
         participation_threshold: f32,
@@ -362,61 +888,106 @@ pub enum PolicyEvaluation {
         vote_counts: VoteCounts,
         majority_threshold: f32,
     },
+    Split {
+        vote_counts: VoteCounts,
+        split: VoteSplit,
+    },
 }
 
 impl PolicyEvaluation {
     pub fn get_aye_count(&self) -> u32 {
         match self {
+            PolicyEvaluation::MinimumVoteCountNotMet { vote_counts, .. } => vote_counts.ayes,
             PolicyEvaluation::AbstainThresholdNotMet { vote_counts, .. } => vote_counts.ayes,
             PolicyEvaluation::ParticipationNotMet { vote_counts, .. } => vote_counts.ayes,
+            PolicyEvaluation::CoreQuorumNotMet { vote_counts, .. } => vote_counts.ayes,
             PolicyEvaluation::MajorityAbstain { vote_counts, .. } => vote_counts.ayes,
             PolicyEvaluation::AyeAbstainMajorityAbstain { vote_counts, .. } => vote_counts.ayes,
             PolicyEvaluation::AyeEqualsNayAbstain { vote_counts, .. } => vote_counts.ayes,
             PolicyEvaluation::Aye { vote_counts, .. } => vote_counts.ayes,
             PolicyEvaluation::Nay { vote_counts, .. } => vote_counts.ayes,
+            PolicyEvaluation::Split { vote_counts, .. } => vote_counts.ayes,
         }
     }
 
     pub fn get_nay_count(&self) -> u32 {
         match self {
+            PolicyEvaluation::MinimumVoteCountNotMet { vote_counts, .. } => vote_counts.nays,
             PolicyEvaluation::AbstainThresholdNotMet { vote_counts, .. } => vote_counts.nays,
             PolicyEvaluation::ParticipationNotMet { vote_counts, .. } => vote_counts.nays,
+            PolicyEvaluation::CoreQuorumNotMet { vote_counts, .. } => vote_counts.nays,
             PolicyEvaluation::MajorityAbstain { vote_counts, .. } => vote_counts.nays,
             PolicyEvaluation::AyeAbstainMajorityAbstain { vote_counts, .. } => vote_counts.nays,
             PolicyEvaluation::AyeEqualsNayAbstain { vote_counts, .. } => vote_counts.nays,
             PolicyEvaluation::Aye { vote_counts, .. } => vote_counts.nays,
             PolicyEvaluation::Nay { vote_counts, .. } => vote_counts.nays,
+            PolicyEvaluation::Split { vote_counts, .. } => vote_counts.nays,
         }
     }
 
     pub fn get_abstain_count(&self) -> u32 {
         match self {
+            PolicyEvaluation::MinimumVoteCountNotMet { vote_counts, .. } => vote_counts.abstains,
             PolicyEvaluation::AbstainThresholdNotMet { vote_counts, .. } => vote_counts.abstains,
             PolicyEvaluation::ParticipationNotMet { vote_counts, .. } => vote_counts.abstains,
+            PolicyEvaluation::CoreQuorumNotMet { vote_counts, .. } => vote_counts.abstains,
             PolicyEvaluation::MajorityAbstain { vote_counts, .. } => vote_counts.abstains,
             PolicyEvaluation::AyeAbstainMajorityAbstain { vote_counts, .. } => vote_counts.abstains,
             PolicyEvaluation::AyeEqualsNayAbstain { vote_counts, .. } => vote_counts.abstains,
             PolicyEvaluation::Aye { vote_counts, .. } => vote_counts.abstains,
             PolicyEvaluation::Nay { vote_counts, .. } => vote_counts.abstains,
+            PolicyEvaluation::Split { vote_counts, .. } => vote_counts.abstains,
         }
     }
 
+    /// Simplifies the outcome to aye, nay or abstain. Split votes simplify to their leading
+    /// side, see `on_chain_vote` for the vote to submit.
     pub fn simplify(&self) -> anyhow::Result<Option<bool>> {
         match self {
+            PolicyEvaluation::MinimumVoteCountNotMet { .. } => {
+                anyhow::bail!("Outcome is no vote.")
+            }
             PolicyEvaluation::AbstainThresholdNotMet { .. } => Ok(None),
             PolicyEvaluation::ParticipationNotMet { .. } => {
                 anyhow::bail!("Outcome is no vote.")
             }
+            PolicyEvaluation::CoreQuorumNotMet { .. } => {
+                anyhow::bail!("Outcome is no vote.")
+            }
             PolicyEvaluation::MajorityAbstain { .. } => Ok(None),
             PolicyEvaluation::AyeAbstainMajorityAbstain { .. } => Ok(None),
             PolicyEvaluation::AyeEqualsNayAbstain { .. } => Ok(None),
             PolicyEvaluation::Aye { .. } => Ok(Some(true)),
             PolicyEvaluation::Nay { .. } => Ok(Some(false)),
+            PolicyEvaluation::Split { split, .. } => Ok(match split.ayes.cmp(&split.nays) {
+                std::cmp::Ordering::Greater => Some(true),
+                std::cmp::Ordering::Less => Some(false),
+                std::cmp::Ordering::Equal => None,
+            }),
+        }
+    }
+
+    pub fn split(&self) -> Option<VoteSplit> {
+        match self {
+            PolicyEvaluation::Split { split, .. } => Some(*split),
+            _ => None,
+        }
+    }
+
+    pub fn on_chain_vote(&self) -> anyhow::Result<OnChainVote> {
+        match self.split() {
+            Some(split) => Ok(OnChainVote::Split(split)),
+            None => Ok(OnChainVote::from(self.simplify()?)),
         }
     }
 
     pub fn is_no_vote(&self) -> bool {
-        matches!(self, PolicyEvaluation::ParticipationNotMet { .. })
+        matches!(
+            self,
+            PolicyEvaluation::MinimumVoteCountNotMet { .. }
+                | PolicyEvaluation::ParticipationNotMet { .. }
+                | PolicyEvaluation::CoreQuorumNotMet { .. }
+        )
     }
 }
 
@@ -424,87 +995,93 @@ impl PolicyEvaluation {
 mod tests {
     use super::*;
 
+    fn policy_for_track(track: &Track) -> Policy {
+        VotingPolicy::from_toml_str(include_str!("../../../_config/policy/v0.3.toml"), "v0.3")
+            .unwrap()
+            .policy_for_track(track)
+    }
+
     #[test]
     fn test_small_tipper() {
-        let policy = Policy::policy_for_track(&Track::SmallTipper);
+        let policy = policy_for_track(&Track::SmallTipper);
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 0, 0)).0,
-            PolicyEvaluation::AbstainThresholdNotMet {
+            policy.evaluate(&VoteCounts::new(8, 1, 0, 0), None).0,
+            PolicyEvaluation::MinimumVoteCountNotMet {
                 vote_counts: VoteCounts::new(8, 1, 0, 0),
-                abstain_threshold: 2.0,
+                minimum_vote_count: 2,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 0, 1, 0)).0,
-            PolicyEvaluation::AbstainThresholdNotMet {
+            policy.evaluate(&VoteCounts::new(8, 0, 1, 0), None).0,
+            PolicyEvaluation::MinimumVoteCountNotMet {
                 vote_counts: VoteCounts::new(8, 0, 1, 0),
-                abstain_threshold: 2.0,
+                minimum_vote_count: 2,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 0, 0, 1)).0,
-            PolicyEvaluation::AbstainThresholdNotMet {
+            policy.evaluate(&VoteCounts::new(8, 0, 0, 1), None).0,
+            PolicyEvaluation::MinimumVoteCountNotMet {
                 vote_counts: VoteCounts::new(8, 0, 0, 1),
-                abstain_threshold: 2.0,
+                minimum_vote_count: 2,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 1, 0)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 1, 0), None).0,
             PolicyEvaluation::AyeEqualsNayAbstain {
                 vote_counts: VoteCounts::new(8, 1, 1, 0),
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 1, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 1, 1), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 1, 1),
                 majority_threshold: 1.5,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 1, 3)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 1, 3), None).0,
             PolicyEvaluation::Aye {
                 vote_counts: VoteCounts::new(8, 2, 1, 3),
                 majority_threshold: 1.5,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 2, 3)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 2, 3), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 2, 2, 3),
                 majority_threshold: 3.5,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 3, 3)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 3, 3), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 2, 3, 3),
                 majority_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 3, 3)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 3, 3), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 3, 3),
                 majority_threshold: 3.5,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 3, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 3, 2), None).0,
             PolicyEvaluation::Nay {
                 vote_counts: VoteCounts::new(8, 1, 3, 2),
                 majority_threshold: 2.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 3, 4)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 3, 4), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 3, 4),
                 majority_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 4, 3, 0)).0,
+            policy.evaluate(&VoteCounts::new(8, 4, 3, 0), None).0,
             PolicyEvaluation::Aye {
                 vote_counts: VoteCounts::new(8, 4, 3, 0),
                 majority_threshold: 3.5,
@@ -512,95 +1089,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_small_spender() {
+        let policy = policy_for_track(&Track::SmallSpender);
+        assert_eq!(
+            policy.evaluate(&VoteCounts::new(8, 2, 0, 0), None).0,
+            PolicyEvaluation::MinimumVoteCountNotMet {
+                vote_counts: VoteCounts::new(8, 2, 0, 0),
+                minimum_vote_count: 3,
+            },
+        );
+        assert_eq!(
+            policy.evaluate(&VoteCounts::new(8, 3, 0, 0), None).0,
+            PolicyEvaluation::Aye {
+                vote_counts: VoteCounts::new(8, 3, 0, 0),
+                majority_threshold: 1.5,
+            },
+        );
+        assert_eq!(
+            policy.evaluate(&VoteCounts::new(16, 3, 0, 0), None).0,
+            PolicyEvaluation::AbstainThresholdNotMet {
+                vote_counts: VoteCounts::new(16, 3, 0, 0),
+                abstain_threshold: 6.0,
+            },
+        );
+        assert!(policy
+            .evaluate(&VoteCounts::new(8, 2, 0, 0), None)
+            .0
+            .is_no_vote());
+    }
+
     #[test]
     fn test_medium_spender() {
-        let policy = Policy::policy_for_track(&Track::MediumSpender);
+        let policy = policy_for_track(&Track::MediumSpender);
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 0, 0)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 0, 0), None).0,
             PolicyEvaluation::ParticipationNotMet {
                 vote_counts: VoteCounts::new(8, 1, 0, 0),
                 participation_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 1, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 1, 1), None).0,
             PolicyEvaluation::ParticipationNotMet {
                 vote_counts: VoteCounts::new(8, 1, 1, 1),
                 participation_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 1, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 1, 2), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 1, 2),
                 majority_threshold: 2.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 1, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 1, 2), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 2, 1, 2),
                 majority_threshold: 2.5,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 3, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 3, 2), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 2, 3, 2),
                 majority_threshold: 3.5,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 4, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 4, 2), None).0,
             PolicyEvaluation::Nay {
                 vote_counts: VoteCounts::new(8, 2, 4, 2),
                 majority_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 3, 4)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 3, 4), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 3, 4),
                 majority_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 4, 3, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 4, 3, 1), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 4, 3, 1),
                 majority_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 4, 2, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 4, 2, 1), None).0,
             PolicyEvaluation::Aye {
                 vote_counts: VoteCounts::new(8, 4, 2, 1),
                 majority_threshold: 3.5,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 5, 3, 0)).0,
+            policy.evaluate(&VoteCounts::new(8, 5, 3, 0), None).0,
             PolicyEvaluation::Aye {
                 vote_counts: VoteCounts::new(8, 5, 3, 0),
                 majority_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 4, 3, 0)).0,
+            policy.evaluate(&VoteCounts::new(8, 4, 3, 0), None).0,
             PolicyEvaluation::Aye {
                 vote_counts: VoteCounts::new(8, 4, 3, 0),
                 majority_threshold: 3.5,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 4, 3, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 4, 3, 1), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 4, 3, 1),
                 majority_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 2, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 2, 1), None).0,
             PolicyEvaluation::Nay {
                 vote_counts: VoteCounts::new(8, 1, 2, 1),
                 majority_threshold: 2.0,
@@ -610,107 +1217,107 @@ mod tests {
 
     #[test]
     fn test_big_spender() {
-        let policy = Policy::policy_for_track(&Track::BigSpender);
+        let policy = policy_for_track(&Track::BigSpender);
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 0, 0)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 0, 0), None).0,
             PolicyEvaluation::ParticipationNotMet {
                 vote_counts: VoteCounts::new(8, 1, 0, 0),
                 participation_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 1, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 1, 1), None).0,
             PolicyEvaluation::ParticipationNotMet {
                 vote_counts: VoteCounts::new(8, 1, 1, 1),
                 participation_threshold: 4.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 1, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 1, 2), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 1, 2),
                 majority_threshold: 2.4,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 3, 1, 0)).0,
+            policy.evaluate(&VoteCounts::new(8, 3, 1, 0), None).0,
             PolicyEvaluation::Aye {
                 vote_counts: VoteCounts::new(8, 3, 1, 0),
                 majority_threshold: 2.4,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 1, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 1, 1), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 2, 1, 1),
                 majority_threshold: 2.4,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 1, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 1, 2), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 2, 1, 2),
                 majority_threshold: 3.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 2, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 2, 2), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 2, 2, 2),
                 majority_threshold: 3.6,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 3, 1)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 3, 1), None).0,
             PolicyEvaluation::Nay {
                 vote_counts: VoteCounts::new(8, 2, 3, 1),
                 majority_threshold: 3.6,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 3, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 3, 2), None).0,
             PolicyEvaluation::Nay {
                 vote_counts: VoteCounts::new(8, 2, 3, 2),
                 majority_threshold: 4.2,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 4, 2)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 4, 2), None).0,
             PolicyEvaluation::Nay {
                 vote_counts: VoteCounts::new(8, 2, 4, 2),
                 majority_threshold: 4.8,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 2, 1, 3)).0,
+            policy.evaluate(&VoteCounts::new(8, 2, 1, 3), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 2, 1, 3),
                 majority_threshold: 3.6,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 1, 4)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 1, 4), None).0,
             PolicyEvaluation::MajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 1, 4),
                 majority_threshold: 3.0,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 3, 4)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 3, 4), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 3, 4),
                 majority_threshold: 4.8,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 4, 0, 0)).0,
+            policy.evaluate(&VoteCounts::new(8, 4, 0, 0), None).0,
             PolicyEvaluation::Aye {
                 vote_counts: VoteCounts::new(8, 4, 0, 0),
                 majority_threshold: 2.4,
             },
         );
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 1, 3, 4)).0,
+            policy.evaluate(&VoteCounts::new(8, 1, 3, 4), None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
                 vote_counts: VoteCounts::new(8, 1, 3, 4),
                 majority_threshold: 4.8,
@@ -720,27 +1327,505 @@ mod tests {
 
     #[test]
     fn test_root() {
-        let policy = Policy::policy_for_track(&Track::Root);
+        let policy = policy_for_track(&Track::Root);
+        let vote_counts = weighted_vote_counts((3, 2, 1, 0), (5, 3, 2, 0));
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 5, 3, 0)).0,
+            policy.evaluate(&vote_counts, None).0,
             PolicyEvaluation::Aye {
-                vote_counts: VoteCounts::new(8, 5, 3, 0),
+                vote_counts,
                 majority_threshold: 4.8,
             },
         );
+        let vote_counts = weighted_vote_counts((3, 2, 0, 1), (5, 3, 2, 0));
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 5, 2, 1)).0,
+            policy.evaluate(&vote_counts, None).0,
             PolicyEvaluation::Aye {
-                vote_counts: VoteCounts::new(8, 5, 2, 1),
+                vote_counts,
                 majority_threshold: 4.8,
             },
         );
+        let vote_counts = weighted_vote_counts((3, 2, 1, 0), (5, 2, 1, 2));
         assert_eq!(
-            policy.evaluate(&VoteCounts::new(8, 4, 2, 2)).0,
+            policy.evaluate(&vote_counts, None).0,
             PolicyEvaluation::AyeAbstainMajorityAbstain {
-                vote_counts: VoteCounts::new(8, 4, 2, 2),
+                vote_counts,
                 majority_threshold: 4.8,
             },
         );
+        // the same totals without the core members
+        let vote_counts = weighted_vote_counts((3, 0, 0, 0), (5, 5, 3, 0));
+        assert_eq!(
+            policy.evaluate(&vote_counts, None).0,
+            PolicyEvaluation::CoreQuorumNotMet {
+                vote_counts,
+                core_quorum_threshold: 1.5,
+            },
+        );
+        // the majority of all votes is met, but not the core majority
+        let vote_counts = weighted_vote_counts((3, 1, 2, 0), (5, 4, 1, 0));
+        assert_eq!(
+            policy.evaluate(&vote_counts, None).0,
+            PolicyEvaluation::AyeAbstainMajorityAbstain {
+                vote_counts,
+                majority_threshold: 4.8,
+            },
+        );
+    }
+
+    fn weighted_vote_counts(
+        core: (u32, u32, u32, u32),
+        community: (u32, u32, u32, u32),
+    ) -> VoteCounts {
+        let membership_vote_counts = |(members, ayes, nays, abstains)| MembershipVoteCounts {
+            members,
+            ayes,
+            nays,
+            abstains,
+        };
+        VoteCounts::from_membership_counts(
+            membership_vote_counts(core),
+            membership_vote_counts(community),
+        )
+    }
+
+    #[test]
+    fn test_weighted_votes() {
+        let policy_toml = include_str!("../../../_config/policy/v0.3.toml").replacen(
+            "majority_denominator = \"OfAllVotes\"\n",
+            r#"majority_denominator = "OfAllVotes"
+member_weights = { core = 2, community = 1 }
+"#,
+            1,
+        );
+        let policy = VotingPolicy::from_toml_str(&policy_toml, "v0.3")
+            .unwrap()
+            .policy_for_track(&Track::Root);
+
+        let vote_counts = weighted_vote_counts((3, 1, 0, 0), (5, 4, 0, 0));
+        assert_eq!(
+            policy.evaluate(&vote_counts, None).0,
+            PolicyEvaluation::CoreQuorumNotMet {
+                vote_counts,
+                core_quorum_threshold: 1.5,
+            },
+        );
+        assert!(policy.evaluate(&vote_counts, None).0.is_no_vote());
+        // raw 5 ayes of 7 would be an aye, weighted it is 5 of 9
+        let vote_counts = weighted_vote_counts((3, 0, 2, 0), (5, 5, 0, 0));
+        assert_eq!(
+            policy.evaluate(&vote_counts, None).0,
+            PolicyEvaluation::Nay {
+                vote_counts,
+                majority_threshold: 5.4,
+            },
+        );
+        // weighted majority met, but not the core majority
+        let vote_counts = weighted_vote_counts((3, 1, 1, 0), (5, 5, 0, 0));
+        assert_eq!(
+            policy.evaluate(&vote_counts, None).0,
+            PolicyEvaluation::AyeAbstainMajorityAbstain {
+                vote_counts,
+                majority_threshold: 5.4,
+            },
+        );
+        let vote_counts = weighted_vote_counts((3, 2, 0, 0), (5, 1, 2, 0));
+        let (evaluation, description_lines) = policy.evaluate(&vote_counts, None);
+        assert_eq!(
+            evaluation,
+            PolicyEvaluation::Aye {
+                vote_counts,
+                majority_threshold: 4.2,
+            },
+        );
+        assert_eq!(evaluation.get_aye_count(), 3);
+        assert!(description_lines.contains(&"11 (8) available members".to_string()));
+        assert!(description_lines.contains(&"🟢 5 (3) • 🔴 2 (2) • ⚪️ 0 (0)".to_string()));
+
+        // uniform weights match the unweighted counts
+        let policy = policy_for_track(&Track::Treasurer);
+        assert_eq!(
+            policy
+                .evaluate(&weighted_vote_counts((3, 0, 2, 0), (5, 5, 0, 0)), None)
+                .0,
+            PolicyEvaluation::Aye {
+                vote_counts: weighted_vote_counts((3, 0, 2, 0), (5, 5, 0, 0)),
+                majority_threshold: 4.2,
+            },
+        );
+    }
+
+    #[test]
+    fn test_threshold_boundaries() {
+        // 60% of 5 votes is exactly 3
+        let policy = policy_for_track(&Track::Treasurer);
+        assert!(matches!(
+            policy.evaluate(&VoteCounts::new(5, 3, 2, 0), None).0,
+            PolicyEvaluation::Aye { .. }
+        ));
+        assert!(matches!(
+            policy.evaluate(&VoteCounts::new(10, 3, 2, 0), None).0,
+            PolicyEvaluation::Aye { .. }
+        ));
+        // 37.5% participation of 8 members is exactly 3 votes
+        let policy = policy_for_track(&Track::SmallSpender);
+        assert!(matches!(
+            policy.evaluate(&VoteCounts::new(8, 3, 0, 0), None).0,
+            PolicyEvaluation::Aye { .. }
+        ));
+        assert!(matches!(
+            policy.evaluate(&VoteCounts::new(9, 3, 0, 0), None).0,
+            PolicyEvaluation::AbstainThresholdNotMet { .. }
+        ));
+        let comparison = Comparison::GreaterThanOrEqual(Percentage::try_from(33.33).unwrap());
+        assert!(comparison.holds(3333, 10000));
+        assert!(!comparison.holds(3332, 10000));
+        assert!(!comparison.holds(0, 0));
+    }
+
+    /// Smallest count out of `whole` that satisfies the comparison, computed with
+    /// ceiling division rather than cross-multiplication.
+    fn required_count(comparison: &Comparison, whole: u32) -> u32 {
+        let product = comparison.threshold().basis_points() as u64 * whole as u64;
+        let required = match comparison {
+            Comparison::GreaterThan(_) => product / BASIS_POINTS_PER_UNIT + 1,
+            Comparison::GreaterThanOrEqual(_) => product.div_ceil(BASIS_POINTS_PER_UNIT),
+        };
+        required as u32
+    }
+
+    fn meets(comparison: &Comparison, part: u32, whole: u32) -> bool {
+        whole > 0 && part >= required_count(comparison, whole)
+    }
+
+    fn expected_evaluation(policy: &Policy, vote_counts: &VoteCounts) -> PolicyEvaluation {
+        let participation = vote_counts.ayes + vote_counts.nays + vote_counts.abstains;
+        if let Some(minimum_vote_count) = policy.minimum_vote_count {
+            if participation < minimum_vote_count {
+                return PolicyEvaluation::MinimumVoteCountNotMet {
+                    vote_counts: *vote_counts,
+                    minimum_vote_count,
+                };
+            }
+        }
+        match &policy.participation_requirement {
+            ParticipationRequirement::AbstainBeforePercent(comparison) => {
+                if !meets(comparison, participation, vote_counts.members) {
+                    return PolicyEvaluation::AbstainThresholdNotMet {
+                        vote_counts: *vote_counts,
+                        abstain_threshold: 0.0,
+                    };
+                }
+            }
+            ParticipationRequirement::NoVoteBeforePercent(comparison) => {
+                if !meets(comparison, participation, vote_counts.members) {
+                    return PolicyEvaluation::ParticipationNotMet {
+                        vote_counts: *vote_counts,
+                        participation_threshold: 0.0,
+                    };
+                }
+            }
+        }
+        let core = vote_counts.core();
+        if let Some(core_quorum) = &policy.core_quorum {
+            if !meets(core_quorum, core.participation(), core.members) {
+                return PolicyEvaluation::CoreQuorumNotMet {
+                    vote_counts: *vote_counts,
+                    core_quorum_threshold: 0.0,
+                };
+            }
+        }
+        if vote_counts.abstains > vote_counts.ayes + vote_counts.nays {
+            return PolicyEvaluation::MajorityAbstain {
+                vote_counts: *vote_counts,
+                majority_threshold: 0.0,
+            };
+        }
+        if vote_counts.abstains == 0 && vote_counts.ayes == vote_counts.nays {
+            return PolicyEvaluation::AyeEqualsNayAbstain {
+                vote_counts: *vote_counts,
+            };
+        }
+        let core_majority_met = policy
+            .core_majority
+            .as_ref()
+            .is_none_or(|core_majority| meets(core_majority, core.ayes, core.ayes + core.nays));
+        if core_majority_met
+            && meets(
+                &policy.majority_comparison,
+                policy.majority_nominator.get(vote_counts),
+                policy.majority_denominator.get(vote_counts),
+            )
+        {
+            return PolicyEvaluation::Aye {
+                vote_counts: *vote_counts,
+                majority_threshold: 0.0,
+            };
+        }
+        if meets(
+            &policy.majority_comparison,
+            vote_counts.ayes + vote_counts.abstains,
+            participation,
+        ) {
+            return PolicyEvaluation::AyeAbstainMajorityAbstain {
+                vote_counts: *vote_counts,
+                majority_threshold: 0.0,
+            };
+        }
+        PolicyEvaluation::Nay {
+            vote_counts: *vote_counts,
+            majority_threshold: 0.0,
+        }
+    }
+
+    fn assert_evaluation_matches(policy: &Policy, vote_counts: &VoteCounts) {
+        let evaluation = policy.evaluate(vote_counts, None).0;
+        let expected = expected_evaluation(policy, vote_counts);
+        assert_eq!(
+            std::mem::discriminant(&evaluation),
+            std::mem::discriminant(&expected),
+            "{:?} {vote_counts:?}: got {evaluation:?}, expected {expected:?}",
+            policy.track,
+        );
+    }
+
+    #[test]
+    fn test_boundary_matrix() {
+        for track in enum_iterator::all::<Track>() {
+            let policy = policy_for_track(&track);
+            for members in 1..=100u32 {
+                // participation boundary for every member count
+                for participation in 0..=members {
+                    for vote_counts in [
+                        VoteCounts::new(members, participation, 0, 0),
+                        VoteCounts::new(members, 0, participation, 0),
+                    ] {
+                        assert_evaluation_matches(&policy, &vote_counts);
+                    }
+                }
+                // majority boundaries for every split of a full turnout
+                for ayes in 0..=members {
+                    for nays in 0..=(members - ayes) {
+                        let vote_counts =
+                            VoteCounts::new(members, ayes, nays, members - ayes - nays);
+                        assert_evaluation_matches(&policy, &vote_counts);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_participation_relaxation() {
+        let relaxations = "participation_relaxations = [\
+            { blocks_left = 14400, threshold = 40.0 }, \
+            { blocks_left = 7200, threshold = 25.0 }]\n";
+        let policy_toml = include_str!("../../../_config/policy/v0.3.toml").replace(
+            "tracks = [\"MediumSpender\"]\n",
+            &format!("tracks = [\"MediumSpender\"]\n{relaxations}"),
+        );
+        let policy = VotingPolicy::from_toml_str(&policy_toml, "v0.3")
+            .unwrap()
+            .policy_for_track(&Track::MediumSpender);
+        let vote_counts = VoteCounts::new(8, 3, 0, 0);
+        for blocks_left in [None, Some(20000)] {
+            assert_eq!(
+                policy.evaluate(&vote_counts, blocks_left).0,
+                PolicyEvaluation::ParticipationNotMet {
+                    vote_counts,
+                    participation_threshold: 4.0,
+                },
+            );
+        }
+        assert_eq!(
+            policy.evaluate(&vote_counts, Some(14400)).0,
+            PolicyEvaluation::ParticipationNotMet {
+                vote_counts,
+                participation_threshold: 3.2,
+            },
+        );
+        let (evaluation, description_lines) = policy.evaluate(&vote_counts, Some(7200));
+        assert_eq!(
+            evaluation,
+            PolicyEvaluation::Aye {
+                vote_counts,
+                majority_threshold: 1.5,
+            },
+        );
+        assert!(description_lines
+            .contains(&"⏳ 7200 blocks left, participation ≥50.0% → ≥25.0%".to_string()));
+        assert_eq!(
+            policy
+                .effective_participation_requirement(Some(100))
+                .comparison()
+                .threshold_rate(),
+            25.0,
+        );
+
+        let invalid_relaxation = include_str!("../../../_config/policy/v0.3.toml").replace(
+            "tracks = [\"MediumSpender\"]\n",
+            "tracks = [\"MediumSpender\"]\n\
+            participation_relaxations = [{ blocks_left = 7200, threshold = 60.0 }]\n",
+        );
+        assert!(VotingPolicy::from_toml_str(&invalid_relaxation, "v0.3").is_err());
+    }
+
+    #[test]
+    fn test_proportional_vote() {
+        let policy_toml = include_str!("../../../_config/policy/v0.3.toml").replace(
+            "tracks = [\"MediumSpender\"]\n",
+            "tracks = [\"MediumSpender\"]\nvote_mode = \"Proportional\"\n",
+        );
+        let policy = VotingPolicy::from_toml_str(&policy_toml, "v0.3")
+            .unwrap()
+            .policy_for_track(&Track::MediumSpender);
+        assert_eq!(
+            policy.evaluate(&VoteCounts::new(8, 1, 1, 1), None).0,
+            PolicyEvaluation::ParticipationNotMet {
+                vote_counts: VoteCounts::new(8, 1, 1, 1),
+                participation_threshold: 4.0,
+            },
+        );
+        let split = VoteSplit {
+            ayes: 4,
+            nays: 2,
+            abstains: 1,
+        };
+        let (evaluation, description_lines) = policy.evaluate(&VoteCounts::new(8, 4, 2, 1), None);
+        assert_eq!(
+            evaluation,
+            PolicyEvaluation::Split {
+                vote_counts: VoteCounts::new(8, 4, 2, 1),
+                split,
+            },
+        );
+        assert!(description_lines.contains(&"⚖️ SPLIT".to_string()));
+        assert_eq!(evaluation.simplify().unwrap(), Some(true));
+        assert_eq!(
+            evaluation.on_chain_vote().unwrap(),
+            OnChainVote::Split(split)
+        );
+        assert_eq!(
+            split.split_balance(1_000_000_000_000),
+            (571_428_571_428, 285_714_285_714, 142_857_142_858),
+        );
+        let split = VoteSplit {
+            ayes: 7,
+            nays: 3,
+            abstains: 0,
+        };
+        assert_eq!(split.split_balance(10), (7, 3, 0));
+        assert_eq!(split.split_balance(u128::MAX).0, u128::MAX / 10 * 7 + 3);
+        let split = VoteSplit {
+            ayes: 0,
+            nays: 0,
+            abstains: 0,
+        };
+        assert_eq!(split.split_balance(10), (0, 0, 10));
+    }
+
+    #[test]
+    fn test_vote_balance() {
+        let policy = policy_for_track(&Track::Treasurer);
+        assert_eq!(policy.vote_balance(), VoteBalance::FixedTokens(1));
+        assert_eq!(policy.conviction(), 1);
+        let account_balance = Balance {
+            free: 1_000_000_000_000,
+            reserved: 500_000_000_000,
+            frozen: 0,
+        };
+        assert_eq!(
+            VoteBalance::FixedTokens(10)
+                .resolve(10, &account_balance)
+                .unwrap(),
+            100_000_000_000,
+        );
+        assert!(VoteBalance::FixedTokens(1000)
+            .resolve(10, &account_balance)
+            .is_err());
+        assert_eq!(
+            VoteBalance::PercentOfFreeBalance(Percentage::try_from(37.5).unwrap())
+                .resolve(10, &account_balance)
+                .unwrap(),
+            375_000_000_000,
+        );
+        assert_eq!(
+            VoteBalance::FullVotingPower
+                .resolve(10, &account_balance)
+                .unwrap(),
+            1_500_000_000_000,
+        );
+        assert!(VoteBalance::FullVotingPower
+            .resolve(
+                10,
+                &Balance {
+                    free: 0,
+                    reserved: 0,
+                    frozen: 0,
+                },
+            )
+            .is_err());
+
+        let policy_toml = include_str!("../../../_config/policy/v0.3.toml").replace(
+            "tracks = [\"MediumSpender\"]\n",
+            "tracks = [\"MediumSpender\"]\n\
+            vote_balance = { PercentOfFreeBalance = 25.0 }\n\
+            conviction = 3\n",
+        );
+        let policy = VotingPolicy::from_toml_str(&policy_toml, "v0.3")
+            .unwrap()
+            .policy_for_track(&Track::MediumSpender);
+        assert_eq!(
+            policy.vote_balance(),
+            VoteBalance::PercentOfFreeBalance(Percentage::from_basis_points(2500).unwrap()),
+        );
+        assert_eq!(policy.conviction(), 3);
+        let invalid_conviction = include_str!("../../../_config/policy/v0.3.toml").replace(
+            "tracks = [\"MediumSpender\"]\n",
+            "tracks = [\"MediumSpender\"]\nconviction = 7\n",
+        );
+        assert!(VotingPolicy::from_toml_str(&invalid_conviction, "v0.3").is_err());
+        let full_voting_power = include_str!("../../../_config/policy/v0.3.toml").replace(
+            "tracks = [\"MediumSpender\"]\n",
+            "tracks = [\"MediumSpender\"]\nvote_balance = \"FullVotingPower\"\n",
+        );
+        assert_eq!(
+            VotingPolicy::from_toml_str(&full_voting_power, "v0.3")
+                .unwrap()
+                .policy_for_track(&Track::MediumSpender)
+                .vote_balance(),
+            VoteBalance::FullVotingPower,
+        );
+        let proportional_full_voting_power = full_voting_power.replace(
+            "tracks = [\"MediumSpender\"]\n",
+            "tracks = [\"MediumSpender\"]\nvote_mode = \"Proportional\"\n",
+        );
+        assert!(VotingPolicy::from_toml_str(&proportional_full_voting_power, "v0.3").is_err());
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        let policy_toml = include_str!("../../../_config/policy/v0.3.toml");
+        assert!(VotingPolicy::from_toml_str(policy_toml, "v0.3").is_ok());
+        assert!(VotingPolicy::from_toml_str(policy_toml, "v0.4").is_err());
+        let missing_track = policy_toml.replace("tracks = [\"MediumSpender\"]", "tracks = []");
+        assert!(VotingPolicy::from_toml_str(&missing_track, "v0.3").is_err());
+        let duplicate_track =
+            policy_toml.replace("tracks = [\"MediumSpender\"]", "tracks = [\"Root\"]");
+        assert!(VotingPolicy::from_toml_str(&duplicate_track, "v0.3").is_err());
+        let invalid_threshold = policy_toml.replace("GreaterThan = 50.0", "GreaterThan = 150.0");
+        assert!(VotingPolicy::from_toml_str(&invalid_threshold, "v0.3").is_err());
+        let imprecise_threshold = policy_toml.replace("37.5", "37.125");
+        assert!(VotingPolicy::from_toml_str(&imprecise_threshold, "v0.3").is_err());
+        let vote_balance_percentage = |percent: &str| {
+            policy_toml.replace(
+                "tracks = [\"MediumSpender\"]\n",
+                &format!(
+                    "tracks = [\"MediumSpender\"]\nvote_balance = {{ PercentOfFreeBalance = {percent} }}\n"
+                ),
+            )
+        };
+        assert!(VotingPolicy::from_toml_str(&vote_balance_percentage("0.0"), "v0.3").is_err());
+        assert!(VotingPolicy::from_toml_str(&vote_balance_percentage("100.5"), "v0.3").is_err());
+        assert!(VotingPolicy::from_toml_str(&vote_balance_percentage("100.0"), "v0.3").is_ok());
     }
 }
@@ -2,10 +2,13 @@
 
 use crate::substrate::account_id::AccountId;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
+pub mod audit;
 pub mod err;
 pub mod governance;
 pub mod openai;
+pub mod outbox;
 pub mod substrate;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -46,3 +49,41 @@ pub struct Member {
     pub is_on_leave: bool,
     pub membership_type: MembershipType,
 }
+
+/// Bot permission of a Telegram user, each role including the ones before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, sqlx::Type)]
+#[sqlx(type_name = "varchar")]
+pub enum Role {
+    Observer,
+    Member,
+    VotingAdmin,
+    Admin,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::Observer => "observer",
+            Self::Member => "member",
+            Self::VotingAdmin => "voting_admin",
+            Self::Admin => "admin",
+        };
+        write!(f, "{str}")
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "observer" => Ok(Self::Observer),
+            "member" => Ok(Self::Member),
+            "voting_admin" => Ok(Self::VotingAdmin),
+            "admin" => Ok(Self::Admin),
+            _ => Err(anyhow::anyhow!(
+                "Unknown role {s}, expected one of observer, member, voting_admin or admin."
+            )),
+        }
+    }
+}
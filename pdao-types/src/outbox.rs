@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// External side effect delivered by the outbox worker.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum OutboxEffect {
+    TelegramMessage {
+        chat_id: i64,
+        thread_id: Option<i32>,
+        message: String,
+    },
+    TelegramTopicName {
+        chat_id: i64,
+        thread_id: i32,
+        name: String,
+        has_coi: bool,
+        vote_count_status: String,
+        status_emoji: String,
+    },
+    OpenSquareAppendant {
+        network_id: u32,
+        cid: String,
+        content: String,
+    },
+    /// The vote comment, or a reply to the comment of the first vote. Saved on the vote once
+    /// posted, and announced in the referendum topic.
+    SubSquareComment {
+        network_id: u32,
+        referendum_index: u32,
+        proposed_height: u64,
+        reply_to_comment_cid: Option<String>,
+        content: String,
+        vote_id: u32,
+        chat_id: i64,
+        thread_id: i32,
+    },
+}
+
+impl OutboxEffect {
+    pub fn get_name(&self) -> &str {
+        match self {
+            Self::TelegramMessage { .. } => "Telegram message",
+            Self::TelegramTopicName { .. } => "Telegram topic name",
+            Self::OpenSquareAppendant { .. } => "OpenSquare appendant",
+            Self::SubSquareComment { .. } => "SubSquare comment",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar")]
+pub enum OutboxItemStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+#[derive(Clone, Debug)]
+pub struct OutboxItem {
+    pub id: u32,
+    /// Enqueueing the same key twice delivers the effect once.
+    pub idempotency_key: String,
+    pub effect: OutboxEffect,
+    pub attempt_count: u32,
+}
@@ -7,3 +7,15 @@ pub struct Balance {
     pub reserved: u128,
     pub frozen: u128,
 }
+
+impl Balance {
+    /// Balance the account can spend on fees. The frozen balance can overlap the reserved
+    /// balance, and the account must keep the existential deposit to stay alive.
+    pub fn spendable(&self, existential_deposit: u128) -> u128 {
+        let untouchable = self
+            .frozen
+            .saturating_sub(self.reserved)
+            .max(existential_deposit);
+        self.free.saturating_sub(untouchable)
+    }
+}
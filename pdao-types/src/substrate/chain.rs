@@ -76,6 +76,36 @@ impl Chain {
             block_time_seconds: 6,
         }
     }
+
+    pub fn westend() -> Self {
+        Chain {
+            id: 3,
+            chain: "westend".to_string(),
+            display: "Westend".to_string(),
+            rpc_url: "wss://westend-rpc.polkadot.io".to_string(),
+            asset_hub_rpc_url: "wss://westend-asset-hub-rpc.polkadot.io".to_string(),
+            token_ticker: "WND".to_string(),
+            token_decimals: 12,
+            token_format_decimal_points: 4,
+            ss58_prefix: 42,
+            block_time_seconds: 6,
+        }
+    }
+
+    pub fn paseo() -> Self {
+        Chain {
+            id: 4,
+            chain: "paseo".to_string(),
+            display: "Paseo".to_string(),
+            rpc_url: "wss://rpc.ibp.network/paseo".to_string(),
+            asset_hub_rpc_url: "wss://sys.ibp.network/asset-hub-paseo".to_string(),
+            token_ticker: "PAS".to_string(),
+            token_decimals: 10,
+            token_format_decimal_points: 4,
+            ss58_prefix: 0,
+            block_time_seconds: 6,
+        }
+    }
 }
 
 impl Display for Chain {
@@ -105,6 +135,8 @@ impl FromStr for Chain {
             "kusama asset hub" | "kah" => Ok(Self::kusama_asset_hub()),
             "polkadot" | "dot" => Ok(Self::polkadot()),
             "polkadot asset hub" | "pah" => Ok(Self::polkadot_asset_hub()),
+            "westend" | "westend asset hub" | "wnd" => Ok(Self::westend()),
+            "paseo" | "paseo asset hub" | "pas" => Ok(Self::paseo()),
             _ => Err(ParseChainError(format!("Unknown chain: {s}"))),
         }
     }
@@ -114,10 +146,27 @@ impl Chain {
     pub fn from_id(id: u32) -> Self {
         match id {
             2 => Self::kusama(),
+            3 => Self::westend(),
+            4 => Self::paseo(),
             _ => Self::polkadot(),
         }
     }
 
+    /// Formats a balance in Planck with the chain's token decimals and ticker,
+    /// e.g. `12.3456 DOT`.
+    pub fn format_balance(&self, balance: u128) -> String {
+        let divisor = 10u128.pow(self.token_decimals as u32);
+        let fraction_divisor =
+            10u128.pow((self.token_decimals - self.token_format_decimal_points) as u32);
+        format!(
+            "{}.{:0width$} {}",
+            balance / divisor,
+            (balance % divisor) / fraction_divisor,
+            self.token_ticker,
+            width = self.token_format_decimal_points,
+        )
+    }
+
     pub fn sp_core_set_default_ss58_version(&self) {
         sp_core::crypto::set_default_ss58_version(Ss58AddressFormat::from(self.ss58_prefix));
     }
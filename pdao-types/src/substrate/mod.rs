@@ -6,5 +6,6 @@ pub mod block;
 pub mod chain;
 pub mod event;
 pub mod identity;
+pub mod payment;
 pub mod referendum;
 pub mod system;
@@ -0,0 +1,19 @@
+use frame_support::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeDispatchInfo {
+    pub class: String,
+    pub partial_fee: String,
+}
+
+impl RuntimeDispatchInfo {
+    pub fn get_partial_fee(&self) -> anyhow::Result<u128> {
+        let partial_fee = if let Some(hex) = self.partial_fee.strip_prefix("0x") {
+            u128::from_str_radix(hex, 16)?
+        } else {
+            self.partial_fee.parse()?
+        };
+        Ok(partial_fee)
+    }
+}
@@ -1,5 +1,91 @@
-#[derive(Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct ReferendumLookup {
     pub hash: [u8; 32],
     pub length: u32,
 }
+
+/// A call of a referendum proposal decoded from its preimage, with the calls it dispatches.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalCall {
+    pub pallet: String,
+    pub call: String,
+    /// Human-readable arguments, e.g. `amount: 100.0000 DOT`.
+    pub args: Vec<String>,
+    pub calls: Vec<ProposalCall>,
+}
+
+impl ProposalCall {
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.pallet, self.call)
+    }
+
+    fn append_lines(&self, prefix: &str, depth: usize, lines: &mut Vec<String>) {
+        let indent = "  ".repeat(depth);
+        lines.push(format!("{prefix}{indent}{}", self.name()));
+        for arg in self.args.iter() {
+            lines.push(format!("{prefix}{indent}  {arg}"));
+        }
+        for call in self.calls.iter() {
+            call.append_lines(prefix, depth + 1, lines);
+        }
+    }
+
+    /// The call tree, one indented line per call and argument.
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        self.append_lines("", 0, &mut lines);
+        lines
+    }
+
+    fn append_diff_lines(&self, new: &ProposalCall, depth: usize, lines: &mut Vec<String>) {
+        let indent = "  ".repeat(depth);
+        if self.name() != new.name() {
+            self.append_lines("- ", depth, lines);
+            new.append_lines("+ ", depth, lines);
+            return;
+        }
+        if self == new {
+            lines.push(format!("  {indent}{} (unchanged)", self.name()));
+            return;
+        }
+        lines.push(format!("  {indent}{}", self.name()));
+        // arguments of the same call are positional, compare them one by one
+        for index in 0..self.args.len().max(new.args.len()) {
+            match (self.args.get(index), new.args.get(index)) {
+                (Some(old_arg), Some(new_arg)) if old_arg == new_arg => {
+                    lines.push(format!("  {indent}  {old_arg}"));
+                }
+                (maybe_old_arg, maybe_new_arg) => {
+                    if let Some(old_arg) = maybe_old_arg {
+                        lines.push(format!("- {indent}  {old_arg}"));
+                    }
+                    if let Some(new_arg) = maybe_new_arg {
+                        lines.push(format!("+ {indent}  {new_arg}"));
+                    }
+                }
+            }
+        }
+        for index in 0..self.calls.len().max(new.calls.len()) {
+            match (self.calls.get(index), new.calls.get(index)) {
+                (Some(old_call), Some(new_call)) => {
+                    old_call.append_diff_lines(new_call, depth + 1, lines)
+                }
+                (Some(old_call), None) => old_call.append_lines("- ", depth + 1, lines),
+                (None, Some(new_call)) => new_call.append_lines("+ ", depth + 1, lines),
+                (None, None) => (),
+            }
+        }
+    }
+
+    /// Call-level diff to the new call tree, with removed lines prefixed by `-` and added lines
+    /// by `+`. Identical nested calls are collapsed into a single line.
+    pub fn get_diff_lines(&self, new: &ProposalCall) -> Vec<String> {
+        let mut lines = Vec::new();
+        self.append_diff_lines(new, 0, &mut lines);
+        lines
+    }
+}
@@ -0,0 +1,371 @@
+use crate::runtime::dynamic::{value_to_bytes, value_to_conviction_vote, value_to_u128};
+use crate::Voter;
+use pdao_types::governance::ConvictionVote;
+use pdao_types::substrate::account_id::AccountId;
+use pdao_types::substrate::chain::Chain;
+use pdao_types::substrate::referendum::ProposalCall;
+use subxt::dynamic::{At, Value};
+use subxt::ext::scale_value::{Composite, ValueDef};
+use subxt::metadata::types::Metadata;
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// Longest rendering of a generic argument before it gets cut.
+const MAX_ARG_LENGTH: usize = 80;
+
+/// Asset hub assets with a known ticker and decimals, by asset id.
+const KNOWN_ASSETS: [(u128, &str, u32); 2] = [(1337, "USDC", 6), (1984, "USDT", 6)];
+
+fn format_amount(amount: u128, decimals: u32, ticker: &str) -> String {
+    let divisor = 10u128.pow(decimals);
+    let fraction_points = decimals.min(4);
+    let fraction_divisor = 10u128.pow(decimals - fraction_points);
+    format!(
+        "{}.{:0width$} {ticker}",
+        amount / divisor,
+        (amount % divisor) / fraction_divisor,
+        width = fraction_points as usize,
+    )
+}
+
+fn truncate(text: String) -> String {
+    if text.chars().count() <= MAX_ARG_LENGTH {
+        text
+    } else {
+        format!(
+            "{}…",
+            text.chars().take(MAX_ARG_LENGTH - 1).collect::<String>()
+        )
+    }
+}
+
+/// Finds the first value of the variant with the given name, e.g. `GeneralIndex`.
+fn find_variant<'a, T>(value: &'a Value<T>, name: &str) -> Option<&'a Value<T>> {
+    match &value.value {
+        ValueDef::Variant(variant) => {
+            if variant.name == name {
+                return variant.values.values().next();
+            }
+            variant
+                .values
+                .values()
+                .find_map(|value| find_variant(value, name))
+        }
+        ValueDef::Composite(composite) => composite
+            .values()
+            .find_map(|value| find_variant(value, name)),
+        _ => None,
+    }
+}
+
+/// Finds a 32-byte account id in a `MultiAddress`, `Location` or `AccountId32` junction.
+fn find_account<T>(chain: &Chain, value: &Value<T>) -> Option<String> {
+    if let Some(bytes) = value_to_bytes(value) {
+        if let Ok(bytes) = <[u8; 32]>::try_from(bytes) {
+            return Some(AccountId::from(bytes).to_ss58_check_with_version(chain.ss58_prefix));
+        }
+    }
+    match &value.value {
+        ValueDef::Variant(variant) => variant
+            .values
+            .values()
+            .find_map(|value| find_account(chain, value)),
+        ValueDef::Composite(composite) => composite
+            .values()
+            .find_map(|value| find_account(chain, value)),
+        _ => None,
+    }
+}
+
+fn format_asset_amount<T>(chain: &Chain, asset_kind: &Value<T>, amount: u128) -> String {
+    match find_variant(asset_kind, "GeneralIndex").and_then(value_to_u128) {
+        Some(asset_id) => match KNOWN_ASSETS.iter().find(|(id, _, _)| *id == asset_id) {
+            Some((_, ticker, decimals)) => format_amount(amount, *decimals, ticker),
+            None => format!("{amount} of asset #{asset_id}"),
+        },
+        None => chain.format_balance(amount),
+    }
+}
+
+fn format_conviction_vote(chain: &Chain, conviction_vote: &ConvictionVote) -> String {
+    match conviction_vote {
+        ConvictionVote::Standard {
+            aye,
+            conviction,
+            balance,
+        } => format!(
+            "{} {} with {} conviction",
+            if *aye { "AYE" } else { "NAY" },
+            chain.format_balance(*balance),
+            if *conviction == 0 {
+                "0.1x".to_string()
+            } else {
+                format!("{conviction}x")
+            },
+        ),
+        ConvictionVote::Split { aye, nay } => format!(
+            "SPLIT {} AYE / {} NAY",
+            chain.format_balance(*aye),
+            chain.format_balance(*nay),
+        ),
+        ConvictionVote::SplitAbstain { aye, nay, abstain } => format!(
+            "SPLIT {} AYE / {} NAY / {} ABSTAIN",
+            chain.format_balance(*aye),
+            chain.format_balance(*nay),
+            chain.format_balance(*abstain),
+        ),
+    }
+}
+
+fn format_arg<T>(value: &Value<T>) -> String {
+    if let Some(bytes) = value_to_bytes(value) {
+        if bytes.len() > 32 {
+            return format!("{} bytes", bytes.len());
+        }
+        return format!("0x{}", hex::encode(bytes));
+    }
+    truncate(value.to_string())
+}
+
+/// A `RuntimeCall` value is the pallet variant wrapping the call variant.
+fn as_call<T>(value: &Value<T>) -> Option<(&str, &str, &Composite<T>)> {
+    let ValueDef::Variant(pallet) = &value.value else {
+        return None;
+    };
+    let mut values = pallet.values.values();
+    let (Some(call), None) = (values.next(), values.next()) else {
+        return None;
+    };
+    let ValueDef::Variant(call) = &call.value else {
+        return None;
+    };
+    Some((&pallet.name, &call.name, &call.values))
+}
+
+fn collect_calls<T>(chain: &Chain, value: &Value<T>) -> Vec<ProposalCall> {
+    if let Some(call) = value_to_proposal_call(chain, value) {
+        return vec![call];
+    }
+    match &value.value {
+        ValueDef::Composite(composite) => composite
+            .values()
+            .flat_map(|value| collect_calls(chain, value))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn value_to_proposal_call<T>(chain: &Chain, value: &Value<T>) -> Option<ProposalCall> {
+    let (pallet, call, fields) = as_call(value)?;
+    let field = |name: &str| fields.at(name);
+    let mut args = Vec::new();
+    let mut calls = Vec::new();
+    match (pallet, call) {
+        ("Treasury", "spend") => {
+            if let (Some(asset_kind), Some(amount)) =
+                (field("asset_kind"), field("amount").and_then(value_to_u128))
+            {
+                args.push(format!(
+                    "amount: {}",
+                    format_asset_amount(chain, asset_kind, amount)
+                ));
+            }
+            if let Some(beneficiary) = field("beneficiary").and_then(|b| find_account(chain, b)) {
+                args.push(format!("beneficiary: {beneficiary}"));
+            }
+            if let Some(valid_from) = field("valid_from").and_then(|v| find_variant(v, "Some")) {
+                args.push(format!("valid from: {}", format_arg(valid_from)));
+            }
+        }
+        ("Treasury", "spend_local") => {
+            if let Some(amount) = field("amount").and_then(value_to_u128) {
+                args.push(format!("amount: {}", chain.format_balance(amount)));
+            }
+            if let Some(beneficiary) = field("beneficiary").and_then(|b| find_account(chain, b)) {
+                args.push(format!("beneficiary: {beneficiary}"));
+            }
+        }
+        ("Proxy", "proxy") => {
+            if let Some(real) = field("real").and_then(|real| find_account(chain, real)) {
+                args.push(format!("real: {real}"));
+            }
+            if let Some(call) = field("call") {
+                calls.extend(collect_calls(chain, call));
+            }
+        }
+        ("ConvictionVoting", "vote") => {
+            if let Some(poll_index) = field("poll_index").and_then(value_to_u128) {
+                args.push(format!("referendum: {poll_index}"));
+            }
+            if let Some(vote) = field("vote").and_then(value_to_conviction_vote) {
+                args.push(format!("vote: {}", format_conviction_vote(chain, &vote)));
+            }
+        }
+        ("ConvictionVoting", "remove_vote") => {
+            if let Some(index) = field("index").and_then(value_to_u128) {
+                args.push(format!("referendum: {index}"));
+            }
+            if let Some(class) = field("class")
+                .and_then(|class| find_variant(class, "Some"))
+                .and_then(value_to_u128)
+            {
+                args.push(format!("class: {class}"));
+            }
+        }
+        ("ConvictionVoting", "unlock") => {
+            if let Some(class) = field("class").and_then(value_to_u128) {
+                args.push(format!("class: {class}"));
+            }
+            if let Some(target) = field("target").and_then(|target| find_account(chain, target)) {
+                args.push(format!("target: {target}"));
+            }
+        }
+        ("System", "set_code" | "set_code_without_checks") => {
+            if let Some(code) = field("code").and_then(value_to_bytes) {
+                args.push(format!("runtime upgrade, code of {} bytes", code.len()));
+            }
+        }
+        ("System", "authorize_upgrade" | "authorize_upgrade_without_checks") => {
+            if let Some(code_hash) = field("code_hash").and_then(value_to_bytes) {
+                args.push(format!(
+                    "runtime upgrade, code hash 0x{}",
+                    hex::encode(code_hash)
+                ));
+            }
+        }
+        _ => {
+            // calls wrapping other calls, like batches, whitelisted dispatches or proxies
+            let named_fields: Vec<(Option<&str>, &Value<T>)> = match fields {
+                Composite::Named(named) => named
+                    .iter()
+                    .map(|(name, value)| (Some(name.as_str()), value))
+                    .collect(),
+                Composite::Unnamed(unnamed) => unnamed.iter().map(|value| (None, value)).collect(),
+            };
+            for (name, value) in named_fields {
+                let nested_calls = collect_calls(chain, value);
+                if nested_calls.is_empty() {
+                    let name = name.map(|name| format!("{name}: ")).unwrap_or_default();
+                    args.push(format!("{name}{}", format_arg(value)));
+                } else {
+                    calls.extend(nested_calls);
+                }
+            }
+        }
+    }
+    Some(ProposalCall {
+        pallet: pallet.to_string(),
+        call: call.to_string(),
+        args,
+        calls,
+    })
+}
+
+/// Decodes an encoded `RuntimeCall`, like a preimage or the call of a built extrinsic, into a
+/// call tree.
+pub(crate) fn decode_call(
+    chain: &Chain,
+    metadata: &Metadata,
+    call_data: &[u8],
+) -> anyhow::Result<ProposalCall> {
+    let value = subxt::ext::scale_value::scale::decode_as_type(
+        &mut &call_data[..],
+        metadata.outer_enums().call_enum_ty(),
+        metadata.types(),
+    )
+    .map_err(|error| anyhow::anyhow!("Cannot decode the call: {error}"))?;
+    value_to_proposal_call(chain, &value).ok_or(anyhow::anyhow!("The data is not a call."))
+}
+
+impl Voter {
+    /// Decodes the preimage against the live runtime metadata into a call tree.
+    pub async fn decode_preimage(
+        &self,
+        chain: &Chain,
+        preimage: &[u8],
+    ) -> anyhow::Result<ProposalCall> {
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        decode_call(chain, &api.metadata(), preimage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::codegen::polkadot::PolkadotRuntime;
+    use crate::runtime::Runtime;
+    use pdao_types::governance::{AccountVote, OnChainVote};
+
+    const ALICE: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+    const BOB: &str = "14E5nqKAp3oAJcmzgZhUD2RcptBeUBScxKHgJKU4HPNcKVf3";
+
+    #[test]
+    fn test_decode_vote_batch() {
+        let chain = Chain::polkadot();
+        let runtime = PolkadotRuntime;
+        let metadata = runtime.get_compiled_metadata().unwrap().unwrap();
+        let account_votes = vec![
+            AccountVote {
+                address: ALICE.to_string(),
+                vote: OnChainVote::Aye,
+                balance: 10_000_000_000,
+                delegated_balance: 0,
+            },
+            AccountVote {
+                address: BOB.to_string(),
+                vote: OnChainVote::Abstain,
+                balance: 25_000_000_000,
+                delegated_balance: 0,
+            },
+        ];
+        let call_data = runtime
+            .get_vote_batch(123, &account_votes, 1)
+            .unwrap()
+            .encode_call_data(&metadata.clone().into())
+            .unwrap();
+        let lines = decode_call(&chain, &metadata, &call_data)
+            .unwrap()
+            .to_lines();
+        assert_eq!(
+            lines,
+            vec![
+                "Utility.batch_all".to_string(),
+                "  Proxy.proxy".to_string(),
+                format!("    real: {ALICE}"),
+                "    ConvictionVoting.vote".to_string(),
+                "      referendum: 123".to_string(),
+                "      vote: AYE 1.0000 DOT with 1x conviction".to_string(),
+                "  Proxy.proxy".to_string(),
+                format!("    real: {BOB}"),
+                "    ConvictionVoting.vote".to_string(),
+                "      referendum: 123".to_string(),
+                "      vote: SPLIT 0.0000 DOT AYE / 0.0000 DOT NAY / 2.5000 DOT ABSTAIN"
+                    .to_string(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_decode_remove_vote_batch() {
+        let chain = Chain::polkadot();
+        let runtime = PolkadotRuntime;
+        let metadata = runtime.get_compiled_metadata().unwrap().unwrap();
+        let call_data = runtime
+            .get_remove_vote_batch(123, &[ALICE.to_string()])
+            .unwrap()
+            .encode_call_data(&metadata.clone().into())
+            .unwrap();
+        let lines = decode_call(&chain, &metadata, &call_data)
+            .unwrap()
+            .to_lines();
+        assert_eq!(
+            lines,
+            vec![
+                "Utility.batch_all".to_string(),
+                "  Proxy.proxy".to_string(),
+                format!("    real: {ALICE}"),
+                "    ConvictionVoting.remove_vote".to_string(),
+                "      referendum: 123".to_string(),
+            ],
+        );
+    }
+}
@@ -1,11 +1,29 @@
-use pdao_config::Config;
+use crate::offline::OfflineSigner;
+use crate::runtime::get_runtime;
+use pdao_config::{Config, ConflictOfInterestBehavior, Environment, NetworkVoterConfig};
+use pdao_keystore::Keystore;
+use pdao_types::governance::policy::VoteBalance;
+use pdao_types::governance::{AccountVote, OnChainVote};
+use pdao_types::substrate::account_id::AccountId;
 use pdao_types::substrate::chain::Chain;
 use pdao_types::substrate::referendum::ReferendumLookup;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use subxt::{OnlineClient, PolkadotConfig};
 
-mod preimage;
-mod referenda;
-mod remove_vote;
-mod vote;
+mod decode;
+mod metadata;
+mod offline;
+mod reconcile;
+mod runtime;
+mod tx;
+mod unlock;
+
+pub use metadata::MetadataCheck;
+pub use offline::SigningRequest;
+pub use reconcile::ChainVotes;
+pub use unlock::{ExpiredVote, UnlockPlan};
 
 #[subxt::subxt(runtime_metadata_path = "../_metadata/polkadot-metadata.scale")]
 mod polkadot {}
@@ -15,45 +33,183 @@ mod kusama {}
 
 pub struct Voter {
     config: Config,
+    keystore: Arc<Keystore>,
+    offline_signer: OfflineSigner,
+}
+
+/// A vote or vote removal batch that has been built and fee-estimated, but not submitted. The
+/// summary is decoded back from the call data.
+pub struct DryRun {
+    pub call_hex: String,
+    pub summary: Vec<String>,
+    pub estimated_fee: u128,
 }
 
 impl Voter {
-    pub async fn new(config: &Config) -> anyhow::Result<Self> {
+    pub async fn new(config: &Config, keystore: Arc<Keystore>) -> anyhow::Result<Self> {
         Ok(Self {
             config: config.clone(),
+            keystore,
+            offline_signer: OfflineSigner::new(),
         })
     }
 
+    /// Votes are never submitted in the test environment, only simulated.
+    pub fn is_safe_mode(&self) -> bool {
+        self.config.env == Environment::Test
+    }
+
+    pub(crate) fn get_network_config(&self, chain: &Chain) -> anyhow::Result<NetworkVoterConfig> {
+        self.config
+            .voter
+            .get_network_config(&chain.chain)
+            .ok_or(anyhow::anyhow!(
+                "No voter configuration for {}.",
+                chain.display
+            ))
+    }
+
+    /// The proxy account that signs the vote batches on the chain, with the balance it can
+    /// currently spend on fees.
+    pub async fn get_proxy_account_balance(
+        &self,
+        chain: &Chain,
+    ) -> anyhow::Result<(AccountId, u128)> {
+        let network_config = self.get_network_config(chain)?;
+        let substrate_client = self.get_substrate_client(chain).await?;
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        let proxy_account_id = self.get_proxy_account_id(chain, &network_config)?;
+        let balance =
+            Self::get_account_balance(&substrate_client, chain, &proxy_account_id).await?;
+        Ok((
+            proxy_account_id,
+            balance.spendable(Self::get_existential_deposit(&api)?),
+        ))
+    }
+
+    /// Estimated fee of a vote batch for all the configured voting accounts on the chain.
+    pub async fn estimate_vote_fee(&self, chain: &Chain) -> anyhow::Result<u128> {
+        let account_votes: Vec<AccountVote> = self
+            .get_network_config(chain)?
+            .voting_accounts
+            .iter()
+            .map(|voting_account| AccountVote {
+                address: voting_account.address.clone(),
+                vote: OnChainVote::Aye,
+                balance: 0,
+                delegated_balance: 0,
+            })
+            .collect();
+        let dry_run = self.dry_run_vote(chain, 0, &account_votes, 1).await?;
+        Ok(dry_run.estimated_fee)
+    }
+
+    /// Builds the votes of the configured voting accounts on the chain, applying each account's
+    /// conflict-of-interest behavior and resolving the vote balance against its current balance.
+    /// With full voting power, also reads the balance delegated to each aye or nay voting account
+    /// in the track.
+    pub async fn get_account_votes(
+        &self,
+        chain: &Chain,
+        track_id: u16,
+        has_coi: bool,
+        vote: OnChainVote,
+        vote_balance: &VoteBalance,
+    ) -> anyhow::Result<Vec<AccountVote>> {
+        let network_config = self.get_network_config(chain)?;
+        let substrate_client = self.get_substrate_client(chain).await?;
+        let delegations_api = if *vote_balance == VoteBalance::FullVotingPower {
+            let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+            let block_hash = api.blocks().at_latest().await?.hash();
+            Some((get_runtime(chain)?, api, block_hash))
+        } else {
+            None
+        };
+        let mut account_votes = Vec::new();
+        for voting_account in network_config.voting_accounts.iter() {
+            let vote = match (has_coi, voting_account.coi_behavior) {
+                (true, ConflictOfInterestBehavior::Skip) => continue,
+                (true, ConflictOfInterestBehavior::Abstain) => OnChainVote::Abstain,
+                _ => vote,
+            };
+            let account_id = AccountId::from_str(&voting_account.address)?;
+            let account_balance = substrate_client
+                .get_balance(&account_id, None)
+                .await?
+                .ok_or(anyhow::anyhow!(
+                    "Voting account {} not found on {}.",
+                    voting_account.address,
+                    chain.display,
+                ))?;
+            let delegated_balance = match (&delegations_api, vote) {
+                (Some((runtime, api, block_hash)), OnChainVote::Aye | OnChainVote::Nay) => runtime
+                    .get_casting(api, *block_hash, &voting_account.address, track_id)
+                    .await?
+                    .map(|casting| casting.delegated_balance)
+                    .unwrap_or_default(),
+                _ => 0,
+            };
+            account_votes.push(AccountVote {
+                address: voting_account.address.clone(),
+                vote,
+                balance: vote_balance.resolve(chain.token_decimals as u32, &account_balance)?,
+                delegated_balance,
+            });
+        }
+        if account_votes.is_empty() {
+            anyhow::bail!("No voting account left to vote with on {}.", chain.display);
+        }
+        Ok(account_votes)
+    }
+
     pub async fn remove_vote(
         &self,
         chain: &Chain,
         referendum_index: u32,
+        account_addresses: &[String],
     ) -> anyhow::Result<(String, u64, u32)> {
-        match chain.chain.as_str() {
-            "polkadot" => self.remove_vote_polkadot(chain, referendum_index).await,
-            _ => self.remove_vote_kusama(chain, referendum_index).await,
-        }
+        let call =
+            get_runtime(chain)?.get_remove_vote_batch(referendum_index, account_addresses)?;
+        let network_config = self.get_network_config(chain)?;
+        self.submit(chain, &network_config, &call).await
     }
 
     pub async fn vote(
         &self,
         chain: &Chain,
         referendum_index: u32,
-        has_coi: bool,
-        vote: Option<bool>,
-        balance: u128,
+        account_votes: &[AccountVote],
         conviction: u8,
     ) -> anyhow::Result<(String, u64, u32)> {
-        match chain.chain.as_str() {
-            "polkadot" => {
-                self.vote_polkadot(chain, referendum_index, has_coi, vote, balance, conviction)
-                    .await
-            }
-            _ => {
-                self.vote_kusama(chain, referendum_index, has_coi, vote, balance, conviction)
-                    .await
-            }
-        }
+        let call =
+            get_runtime(chain)?.get_vote_batch(referendum_index, account_votes, conviction)?;
+        let network_config = self.get_network_config(chain)?;
+        self.submit(chain, &network_config, &call).await
+    }
+
+    pub async fn dry_run_remove_vote(
+        &self,
+        chain: &Chain,
+        referendum_index: u32,
+        account_addresses: &[String],
+    ) -> anyhow::Result<DryRun> {
+        let call =
+            get_runtime(chain)?.get_remove_vote_batch(referendum_index, account_addresses)?;
+        let network_config = self.get_network_config(chain)?;
+        self.dry_run(chain, &network_config, &call).await
+    }
+
+    pub async fn dry_run_vote(
+        &self,
+        chain: &Chain,
+        referendum_index: u32,
+        account_votes: &[AccountVote],
+        conviction: u8,
+    ) -> anyhow::Result<DryRun> {
+        let call =
+            get_runtime(chain)?.get_vote_batch(referendum_index, account_votes, conviction)?;
+        let network_config = self.get_network_config(chain)?;
+        self.dry_run(chain, &network_config, &call).await
     }
 
     pub async fn get_referendum_lookup(
@@ -61,16 +217,9 @@ impl Voter {
         chain: &Chain,
         referendum_index: u32,
     ) -> anyhow::Result<Option<ReferendumLookup>> {
-        let result = match chain.chain.as_str() {
-            "polkadot" => {
-                referenda::polkadot::get_referendum_lookup_polkadot(chain, referendum_index).await?
-            }
-            "kusama" => {
-                referenda::kusama::get_referendum_lookup_kusama(chain, referendum_index).await?
-            }
-            _ => None,
-        };
-        Ok(result)
+        let runtime = get_runtime(chain)?;
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        runtime.get_referendum_lookup(&api, referendum_index).await
     }
 
     pub async fn get_preimage(
@@ -78,11 +227,33 @@ impl Voter {
         chain: &Chain,
         lookup: &ReferendumLookup,
     ) -> anyhow::Result<Option<Vec<u8>>> {
-        let result = match chain.chain.as_str() {
-            "polkadot" => preimage::polkadot::get_preimage_polkadot(chain, lookup).await?,
-            "kusama" => preimage::kusama::get_preimage_kusama(chain, lookup).await?,
-            _ => None,
-        };
-        Ok(result)
+        let runtime = get_runtime(chain)?;
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        runtime.get_preimage(&api, lookup).await
+    }
+
+    /// The lookup of each referendum and whether its preimage exists, read over one connection.
+    pub async fn get_preimage_states(
+        &self,
+        chain: &Chain,
+        referendum_indices: &[u32],
+    ) -> anyhow::Result<BTreeMap<u32, (Option<ReferendumLookup>, bool)>> {
+        let mut preimage_states = BTreeMap::new();
+        if referendum_indices.is_empty() {
+            return Ok(preimage_states);
+        }
+        let runtime = get_runtime(chain)?;
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        for referendum_index in referendum_indices.iter() {
+            let maybe_lookup = runtime
+                .get_referendum_lookup(&api, *referendum_index)
+                .await?;
+            let preimage_exists = match maybe_lookup.as_ref() {
+                Some(lookup) => runtime.get_preimage(&api, lookup).await?.is_some(),
+                None => false,
+            };
+            preimage_states.insert(*referendum_index, (maybe_lookup, preimage_exists));
+        }
+        Ok(preimage_states)
     }
 }
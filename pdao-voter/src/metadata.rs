@@ -0,0 +1,114 @@
+use crate::runtime::get_runtime;
+use crate::Voter;
+use pdao_types::substrate::chain::Chain;
+use scale_info::TypeDef;
+use subxt::metadata::types::Metadata;
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// Outcome of comparing the metadata a runtime was compiled against with the live chain.
+pub struct MetadataCheck {
+    pub spec_version: u32,
+    pub mismatches: Vec<String>,
+}
+
+impl MetadataCheck {
+    pub fn has_drift(&self) -> bool {
+        !self.mismatches.is_empty()
+    }
+}
+
+/// The compiled metadata is stripped down to the pallets the voter uses, including the variants
+/// of `RuntimeCall`. Strip the live metadata the same way so that the hashes of the calls taking
+/// a `RuntimeCall` (`Proxy.proxy`, `Utility.batch_all`) are comparable.
+fn retain_call_enum_pallets(metadata: &mut Metadata, pallet_names: &[String]) {
+    let call_enum_ty = metadata.outer_enums().call_enum_ty();
+    let Some(call_enum) = metadata
+        .types_mut()
+        .types
+        .iter_mut()
+        .find(|portable_type| portable_type.id == call_enum_ty)
+    else {
+        return;
+    };
+    if let TypeDef::Variant(call_enum) = &mut call_enum.ty.type_def {
+        call_enum
+            .variants
+            .retain(|variant| pallet_names.contains(&variant.name));
+    }
+}
+
+fn get_metadata_mismatches(mut compiled: Metadata, mut live: Metadata) -> Vec<String> {
+    let pallet_names: Vec<String> = compiled
+        .pallets()
+        .map(|pallet| pallet.name().to_string())
+        .collect();
+    retain_call_enum_pallets(&mut compiled, &pallet_names);
+    retain_call_enum_pallets(&mut live, &pallet_names);
+    let mut mismatches = Vec::new();
+    for compiled_pallet in compiled.pallets() {
+        let pallet_name = compiled_pallet.name();
+        let Some(live_pallet) = live.pallet_by_name(pallet_name) else {
+            mismatches.push(format!("Pallet {pallet_name} is missing."));
+            continue;
+        };
+        if compiled_pallet.index() != live_pallet.index() {
+            mismatches.push(format!(
+                "Pallet {pallet_name} index changed from {} to {}.",
+                compiled_pallet.index(),
+                live_pallet.index(),
+            ));
+        }
+        for call in compiled_pallet.call_variants().unwrap_or_default() {
+            let Some(live_call) = live_pallet.call_variant_by_name(&call.name) else {
+                mismatches.push(format!("Call {pallet_name}.{} is missing.", call.name));
+                continue;
+            };
+            if call.index != live_call.index {
+                mismatches.push(format!(
+                    "Call {pallet_name}.{} index changed from {} to {}.",
+                    call.name, call.index, live_call.index,
+                ));
+            } else if compiled_pallet.call_hash(&call.name) != live_pallet.call_hash(&call.name) {
+                mismatches.push(format!(
+                    "Call {pallet_name}.{} arguments changed.",
+                    call.name
+                ));
+            }
+        }
+        let storage_entries = compiled_pallet
+            .storage()
+            .map(|storage| storage.entries())
+            .unwrap_or_default();
+        for entry in storage_entries {
+            if compiled_pallet.storage_hash(entry.name()) != live_pallet.storage_hash(entry.name())
+            {
+                mismatches.push(format!("Storage {pallet_name}.{} changed.", entry.name()));
+            }
+        }
+    }
+    mismatches
+}
+
+impl Voter {
+    pub(crate) fn get_metadata_check(
+        chain: &Chain,
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> anyhow::Result<MetadataCheck> {
+        let spec_version = api.runtime_version().spec_version;
+        let mismatches = match get_runtime(chain)?.get_compiled_metadata()? {
+            Some(compiled) => get_metadata_mismatches(compiled, (*api.metadata()).clone()),
+            None => Vec::new(),
+        };
+        Ok(MetadataCheck {
+            spec_version,
+            mismatches,
+        })
+    }
+
+    /// Compares the calls and storage entries of the pallets the voter uses with the live
+    /// runtime. Submissions are refused while they differ.
+    pub async fn check_metadata(&self, chain: &Chain) -> anyhow::Result<MetadataCheck> {
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        Self::get_metadata_check(chain, &api)
+    }
+}
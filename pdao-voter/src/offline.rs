@@ -0,0 +1,254 @@
+use crate::Voter;
+use pdao_types::substrate::account_id::AccountId;
+use pdao_types::substrate::chain::Chain;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use subxt::config::polkadot::PolkadotExtrinsicParamsBuilder;
+use subxt::tx::{PartialTransaction, Payload};
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt::{OnlineClient, PolkadotConfig};
+use subxt_signer::sr25519;
+use tokio::sync::broadcast;
+
+/// Extrinsics signed offline stay valid for this many blocks, so that signing on an air-gapped
+/// device doesn't have to race the default mortality.
+const OFFLINE_SIGNING_MORTALITY_BLOCKS: u64 = 1024;
+const SIGNATURE_POLL_SECONDS: u64 = 2;
+const SIGNING_REQUEST_CHANNEL_CAPACITY: usize = 16;
+
+type PartialExtrinsic = PartialTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+
+/// The signing payload of an extrinsic waiting for a detached signature from the proxy account.
+#[derive(Clone, Debug)]
+pub struct SigningRequest {
+    pub id: String,
+    pub chain: String,
+    pub signer_address: String,
+    pub spec_version: u32,
+    pub metadata_hash: String,
+    pub call_hex: String,
+    /// The bytes to sign, already hashed when longer than 256 bytes.
+    pub payload_hex: String,
+}
+
+pub(crate) struct OfflineSigner {
+    request_sender: broadcast::Sender<SigningRequest>,
+    pending_requests: Mutex<BTreeMap<String, (SigningRequest, [u8; 32])>>,
+    signatures: Mutex<BTreeMap<String, [u8; 64]>>,
+}
+
+impl OfflineSigner {
+    pub(crate) fn new() -> Self {
+        let (request_sender, _) = broadcast::channel(SIGNING_REQUEST_CHANNEL_CAPACITY);
+        Self {
+            request_sender,
+            pending_requests: Mutex::new(BTreeMap::new()),
+            signatures: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+pub(crate) fn get_public_key(account_id: &AccountId) -> [u8; 32] {
+    let mut public_key = [0; 32];
+    public_key.copy_from_slice(account_id.as_ref());
+    public_key
+}
+
+fn verify_signature(
+    payload_hex: &str,
+    signature_hex: &str,
+    public_key: &[u8; 32],
+) -> anyhow::Result<[u8; 64]> {
+    let payload = hex::decode(payload_hex.trim_start_matches("0x"))?;
+    let signature: [u8; 64] = hex::decode(signature_hex.trim().trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("The signature must be 64 bytes."))?;
+    if !sr25519::verify(
+        &sr25519::Signature(signature),
+        payload,
+        &sr25519::PublicKey(*public_key),
+    ) {
+        anyhow::bail!("The signature does not match the payload and the proxy account.");
+    }
+    Ok(signature)
+}
+
+impl Voter {
+    pub fn is_offline_signing(&self) -> bool {
+        self.config.voter.offline_signing
+    }
+
+    /// Signing requests as they are created, to be forwarded to the admins.
+    pub fn subscribe_signing_requests(&self) -> broadcast::Receiver<SigningRequest> {
+        self.offline_signer.request_sender.subscribe()
+    }
+
+    /// Accepts the detached signature of a pending signing request after verifying it.
+    pub fn submit_signature(&self, request_id: &str, signature_hex: &str) -> anyhow::Result<()> {
+        let pending_requests = self.offline_signer.pending_requests.lock().unwrap();
+        let Some((request, public_key)) = pending_requests.get(request_id) else {
+            anyhow::bail!("No pending signing request with id {request_id}.");
+        };
+        let signature = verify_signature(&request.payload_hex, signature_hex, public_key)?;
+        self.offline_signer
+            .signatures
+            .lock()
+            .unwrap()
+            .insert(request_id.to_string(), signature);
+        Ok(())
+    }
+
+    fn get_offline_signing_file_path(&self, request_id: &str, extension: &str) -> PathBuf {
+        PathBuf::from(&self.config.voter.offline_signing_dir_path)
+            .join(format!("{request_id}.{extension}"))
+    }
+
+    /// Waits for the signature through `submit_signature` or a `<request_id>.signature` file.
+    async fn wait_for_signature(
+        &self,
+        request: &SigningRequest,
+        public_key: &[u8; 32],
+    ) -> anyhow::Result<[u8; 64]> {
+        let signature_file_path = self.get_offline_signing_file_path(&request.id, "signature");
+        let timeout = Duration::from_secs(self.config.voter.offline_signing_timeout_seconds);
+        let start = Instant::now();
+        while start.elapsed() < timeout {
+            if let Some(signature) = self
+                .offline_signer
+                .signatures
+                .lock()
+                .unwrap()
+                .remove(&request.id)
+            {
+                return Ok(signature);
+            }
+            if let Ok(signature_hex) = std::fs::read_to_string(&signature_file_path) {
+                match verify_signature(&request.payload_hex, &signature_hex, public_key) {
+                    Ok(signature) => return Ok(signature),
+                    Err(error) => log::warn!(
+                        "Invalid signature file {}: {error}",
+                        signature_file_path.display()
+                    ),
+                }
+                if let Err(error) = std::fs::remove_file(&signature_file_path) {
+                    log::warn!(
+                        "Cannot remove invalid signature file {}: {error}",
+                        signature_file_path.display()
+                    );
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(SIGNATURE_POLL_SECONDS)).await;
+        }
+        anyhow::bail!(
+            "No signature received for signing request {} in {} seconds.",
+            request.id,
+            timeout.as_secs(),
+        )
+    }
+
+    async fn get_detached_signature(
+        &self,
+        request: SigningRequest,
+        public_key: [u8; 32],
+    ) -> anyhow::Result<[u8; 64]> {
+        std::fs::create_dir_all(&self.config.voter.offline_signing_dir_path)?;
+        std::fs::write(
+            self.get_offline_signing_file_path(&request.id, "payload"),
+            &request.payload_hex,
+        )?;
+        self.offline_signer
+            .pending_requests
+            .lock()
+            .unwrap()
+            .insert(request.id.clone(), (request.clone(), public_key));
+        // there may be no admin listening, the payload file is still there
+        let _ = self.offline_signer.request_sender.send(request.clone());
+        let result = self.wait_for_signature(&request, &public_key).await;
+        self.offline_signer
+            .pending_requests
+            .lock()
+            .unwrap()
+            .remove(&request.id);
+        self.offline_signer
+            .signatures
+            .lock()
+            .unwrap()
+            .remove(&request.id);
+        let _ = std::fs::remove_file(self.get_offline_signing_file_path(&request.id, "payload"));
+        let _ = std::fs::remove_file(self.get_offline_signing_file_path(&request.id, "signature"));
+        result
+    }
+
+    /// Creates the unsigned extrinsic of the proxy account, with a longer mortality.
+    pub(crate) async fn create_partial_extrinsic<Call: Payload>(
+        api: &OnlineClient<PolkadotConfig>,
+        proxy_account_id: &AccountId,
+        call: &Call,
+    ) -> anyhow::Result<PartialExtrinsic> {
+        let params = PolkadotExtrinsicParamsBuilder::new()
+            .mortal(OFFLINE_SIGNING_MORTALITY_BLOCKS)
+            .build();
+        let account_id = AccountId32(get_public_key(proxy_account_id));
+        Ok(api.tx().create_partial(call, &account_id, params).await?)
+    }
+
+    /// Exports the signing payload of the extrinsic and waits for its detached signature.
+    pub(crate) async fn sign_offline(
+        &self,
+        chain: &Chain,
+        api: &OnlineClient<PolkadotConfig>,
+        proxy_account_id: &AccountId,
+        call_data: &[u8],
+        signer_payload: &[u8],
+    ) -> anyhow::Result<MultiSignature> {
+        let created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        let request = SigningRequest {
+            id: format!("{}-{created_at}", chain.chain),
+            chain: chain.display.clone(),
+            signer_address: proxy_account_id.to_ss58_check_with_version(chain.ss58_prefix),
+            spec_version: api.runtime_version().spec_version,
+            metadata_hash: format!("0x{}", hex::encode(api.metadata().hasher().hash())),
+            call_hex: format!("0x{}", hex::encode(call_data)),
+            payload_hex: format!("0x{}", hex::encode(signer_payload)),
+        };
+        log::info!(
+            "Wait for the signature of {} signing request {}.",
+            chain.display,
+            request.id
+        );
+        let signature = self
+            .get_detached_signature(request, get_public_key(proxy_account_id))
+            .await?;
+        Ok(MultiSignature::Sr25519(signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use subxt_signer::SecretUri;
+
+    fn keypair(uri: &str) -> sr25519::Keypair {
+        sr25519::Keypair::from_uri(&SecretUri::from_str(uri).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let alice = keypair("//Alice");
+        let bob = keypair("//Bob");
+        let payload = b"vote batch signer payload";
+        let payload_hex = format!("0x{}", hex::encode(payload));
+        let signature = alice.sign(payload).0;
+        let signature_hex = format!("0x{}", hex::encode(signature));
+        assert_eq!(
+            verify_signature(&payload_hex, &signature_hex, &alice.public_key().0).unwrap(),
+            signature,
+        );
+        assert!(verify_signature(&payload_hex, &signature_hex, &bob.public_key().0).is_err());
+        assert!(verify_signature("0x00", &signature_hex, &alice.public_key().0).is_err());
+        assert!(verify_signature(&payload_hex, "0x1234", &alice.public_key().0).is_err());
+    }
+}
@@ -0,0 +1,55 @@
+use crate::runtime::get_runtime;
+use crate::Voter;
+use pdao_types::governance::ConvictionVote;
+use pdao_types::substrate::chain::Chain;
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// Votes of the voting accounts on a referendum, read from `ConvictionVoting::VotingFor` at a
+/// finalized block.
+pub struct ChainVotes {
+    pub block_hash: String,
+    pub block_number: u64,
+    pub account_votes: Vec<(String, Option<ConvictionVote>)>,
+}
+
+impl Voter {
+    pub fn get_voting_account_addresses(&self, chain: &Chain) -> anyhow::Result<Vec<String>> {
+        Ok(self
+            .get_network_config(chain)?
+            .voting_accounts
+            .iter()
+            .map(|voting_account| voting_account.address.clone())
+            .collect())
+    }
+
+    pub async fn get_chain_votes(
+        &self,
+        chain: &Chain,
+        track_id: u16,
+        referendum_index: u32,
+        account_addresses: &[String],
+    ) -> anyhow::Result<ChainVotes> {
+        let runtime = get_runtime(chain)?;
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        let block = api.blocks().at_latest().await?;
+        let mut account_votes = Vec::new();
+        for account_address in account_addresses.iter() {
+            let conviction_vote = runtime
+                .get_casting(&api, block.hash(), account_address, track_id)
+                .await?
+                .and_then(|casting| {
+                    casting
+                        .votes
+                        .into_iter()
+                        .find(|(poll_index, _)| *poll_index == referendum_index)
+                })
+                .map(|(_, conviction_vote)| conviction_vote);
+            account_votes.push((account_address.clone(), conviction_vote));
+        }
+        Ok(ChainVotes {
+            block_hash: format!("0x{}", hex::encode(block.hash().0)),
+            block_number: block.number() as u64,
+            account_votes,
+        })
+    }
+}
@@ -1,2 +0,0 @@
-pub(crate) mod kusama;
-pub(crate) mod polkadot;
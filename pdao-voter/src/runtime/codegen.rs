@@ -0,0 +1,300 @@
+//! Runtimes with calls and storage generated by subxt from the metadata in `_metadata`. The
+//! generated modules only differ in their path, so one implementation serves them all.
+
+/// Implements [`Runtime`](crate::runtime::Runtime) in a module over a generated `codegen`
+/// module, `runtime_module` being the runtime's module in its `runtime_types`.
+macro_rules! codegen_runtime {
+    (
+        $module:ident,
+        $runtime:ident,
+        $codegen:ident,
+        $runtime_module:ident,
+        $metadata_path:literal,
+        $doc:literal $(,)?
+    ) => {
+        pub(crate) mod $module {
+            use crate::runtime::{
+                stored_vote_to_conviction_vote, BoxedPayload, Casting, ReferendumOutcome, Runtime,
+            };
+            use crate::unlock::ExpiredVote;
+            use crate::$codegen as codegen;
+            use async_trait::async_trait;
+            use codegen::conviction_voting::calls::types::vote::Vote as VoteCall;
+            use codegen::proxy::calls::types::proxy::Call as ProxiedCall;
+            use codegen::referenda::storage::types::referendum_info_for::ReferendumInfoFor;
+            use codegen::runtime_types::frame_support::traits::preimages::Bounded;
+            use codegen::runtime_types::pallet_conviction_voting::pallet::Call as VotingCall;
+            use codegen::runtime_types::pallet_conviction_voting::vote::{
+                AccountVote as StoredAccountVote, PriorLock, Vote, Voting,
+            };
+            use codegen::runtime_types::pallet_proxy::pallet::Call as ProxyCall;
+            use codegen::runtime_types::$runtime_module::RuntimeCall;
+            use pdao_types::governance::{AccountVote, ConvictionVote, OnChainVote};
+            use pdao_types::substrate::referendum::ReferendumLookup;
+            use std::str::FromStr;
+            use subxt::ext::codec::Decode;
+            use subxt::metadata::types::Metadata;
+            use subxt::utils::{AccountId32, H256};
+            use subxt::{OnlineClient, PolkadotConfig};
+
+            #[doc = $doc]
+            pub(crate) struct $runtime;
+
+            impl $runtime {
+                fn get_proxy_call(
+                    real_account_address: &str,
+                    call: ProxiedCall,
+                ) -> anyhow::Result<RuntimeCall> {
+                    let real = AccountId32::from_str(real_account_address)?;
+                    Ok(RuntimeCall::Proxy(ProxyCall::proxy {
+                        real: real.into(),
+                        force_proxy_type: None,
+                        call: Box::new(call),
+                    }))
+                }
+
+                fn get_vote_call(
+                    real_account_address: &str,
+                    referendum_index: u32,
+                    vote: OnChainVote,
+                    balance: u128,
+                    conviction: u8,
+                ) -> anyhow::Result<RuntimeCall> {
+                    let vote = match ConvictionVote::new(vote, balance, conviction) {
+                        ConvictionVote::Standard {
+                            aye,
+                            conviction,
+                            balance,
+                        } => VoteCall::Standard {
+                            vote: Vote(conviction + if aye { 128 } else { 0 }),
+                            balance,
+                        },
+                        ConvictionVote::Split { aye, nay } => VoteCall::Split { aye, nay },
+                        ConvictionVote::SplitAbstain { aye, nay, abstain } => {
+                            VoteCall::SplitAbstain { aye, nay, abstain }
+                        }
+                    };
+                    Self::get_proxy_call(
+                        real_account_address,
+                        ProxiedCall::ConvictionVoting(VotingCall::vote {
+                            poll_index: referendum_index,
+                            vote,
+                        }),
+                    )
+                }
+            }
+
+            #[async_trait]
+            impl Runtime for $runtime {
+                fn get_compiled_metadata(&self) -> anyhow::Result<Option<Metadata>> {
+                    let bytes = include_bytes!($metadata_path);
+                    Ok(Some(Metadata::decode(&mut &bytes[..])?))
+                }
+
+                fn get_vote_batch(
+                    &self,
+                    referendum_index: u32,
+                    account_votes: &[AccountVote],
+                    conviction: u8,
+                ) -> anyhow::Result<BoxedPayload> {
+                    let mut proxy_calls = Vec::new();
+                    for account_vote in account_votes.iter() {
+                        proxy_calls.push(Self::get_vote_call(
+                            &account_vote.address,
+                            referendum_index,
+                            account_vote.vote,
+                            account_vote.balance,
+                            conviction,
+                        )?);
+                    }
+                    Ok(Box::new(codegen::tx().utility().batch_all(proxy_calls)))
+                }
+
+                fn get_remove_vote_batch(
+                    &self,
+                    referendum_index: u32,
+                    account_addresses: &[String],
+                ) -> anyhow::Result<BoxedPayload> {
+                    let mut proxy_calls = Vec::new();
+                    for account_address in account_addresses.iter() {
+                        proxy_calls.push(Self::get_proxy_call(
+                            account_address,
+                            ProxiedCall::ConvictionVoting(VotingCall::remove_vote {
+                                class: None,
+                                index: referendum_index,
+                            }),
+                        )?);
+                    }
+                    Ok(Box::new(codegen::tx().utility().batch_all(proxy_calls)))
+                }
+
+                async fn get_referendum_lookup(
+                    &self,
+                    api: &OnlineClient<PolkadotConfig>,
+                    referendum_index: u32,
+                ) -> anyhow::Result<Option<ReferendumLookup>> {
+                    let query = codegen::storage()
+                        .referenda()
+                        .referendum_info_for(referendum_index);
+                    let referendum_info = api.storage().at_latest().await?.fetch(&query).await?;
+                    let lookup = match referendum_info {
+                        Some(ReferendumInfoFor::Ongoing(status)) => match status.proposal {
+                            Bounded::Lookup { hash, len } => Some(ReferendumLookup {
+                                hash: hash.0,
+                                length: len,
+                            }),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    Ok(lookup)
+                }
+
+                async fn get_preimage(
+                    &self,
+                    api: &OnlineClient<PolkadotConfig>,
+                    lookup: &ReferendumLookup,
+                ) -> anyhow::Result<Option<Vec<u8>>> {
+                    let query = codegen::storage()
+                        .preimage()
+                        .preimage_for((H256(lookup.hash), lookup.length));
+                    let result = api.storage().at_latest().await?.fetch(&query).await?;
+                    Ok(result.map(|preimage| preimage.0))
+                }
+
+                fn get_unlock_batch(
+                    &self,
+                    expired_votes: &[ExpiredVote],
+                    unlocks: &[(String, u16)],
+                ) -> anyhow::Result<BoxedPayload> {
+                    let mut proxy_calls = Vec::new();
+                    for expired_vote in expired_votes.iter() {
+                        proxy_calls.push(Self::get_proxy_call(
+                            &expired_vote.address,
+                            ProxiedCall::ConvictionVoting(VotingCall::remove_vote {
+                                class: Some(expired_vote.class),
+                                index: expired_vote.referendum_index,
+                            }),
+                        )?);
+                    }
+                    for (account_address, class) in unlocks.iter() {
+                        let target = AccountId32::from_str(account_address)?;
+                        proxy_calls.push(Self::get_proxy_call(
+                            account_address,
+                            ProxiedCall::ConvictionVoting(VotingCall::unlock {
+                                class: *class,
+                                target: target.into(),
+                            }),
+                        )?);
+                    }
+                    Ok(Box::new(codegen::tx().utility().batch_all(proxy_calls)))
+                }
+
+                async fn get_class_locks(
+                    &self,
+                    api: &OnlineClient<PolkadotConfig>,
+                    block_hash: H256,
+                    account_address: &str,
+                ) -> anyhow::Result<Vec<u16>> {
+                    let account_id = AccountId32::from_str(account_address)?;
+                    let query = codegen::storage()
+                        .conviction_voting()
+                        .class_locks_for(account_id);
+                    let class_locks = api.storage().at(block_hash).fetch(&query).await?;
+                    Ok(class_locks
+                        .map(|class_locks| {
+                            class_locks.0.into_iter().map(|(class, _)| class).collect()
+                        })
+                        .unwrap_or_default())
+                }
+
+                async fn get_casting(
+                    &self,
+                    api: &OnlineClient<PolkadotConfig>,
+                    block_hash: H256,
+                    account_address: &str,
+                    class: u16,
+                ) -> anyhow::Result<Option<Casting>> {
+                    let account_id = AccountId32::from_str(account_address)?;
+                    let query = codegen::storage()
+                        .conviction_voting()
+                        .voting_for(account_id, class);
+                    let Some(Voting::Casting(casting)) =
+                        api.storage().at(block_hash).fetch(&query).await?
+                    else {
+                        return Ok(None);
+                    };
+                    let votes = casting
+                        .votes
+                        .0
+                        .into_iter()
+                        .map(|(poll_index, account_vote)| {
+                            let conviction_vote = match account_vote {
+                                StoredAccountVote::Standard { vote, balance } => {
+                                    stored_vote_to_conviction_vote(vote.0, balance)
+                                }
+                                StoredAccountVote::Split { aye, nay } => {
+                                    ConvictionVote::Split { aye, nay }
+                                }
+                                StoredAccountVote::SplitAbstain { aye, nay, abstain } => {
+                                    ConvictionVote::SplitAbstain { aye, nay, abstain }
+                                }
+                            };
+                            (poll_index, conviction_vote)
+                        })
+                        .collect();
+                    let PriorLock(prior_block, prior_balance) = casting.prior;
+                    Ok(Some(Casting {
+                        votes,
+                        prior: (prior_block, prior_balance),
+                        delegated_balance: casting.delegations.capital,
+                    }))
+                }
+
+                async fn get_referendum_outcome(
+                    &self,
+                    api: &OnlineClient<PolkadotConfig>,
+                    block_hash: H256,
+                    referendum_index: u32,
+                ) -> anyhow::Result<Option<ReferendumOutcome>> {
+                    let query = codegen::storage()
+                        .referenda()
+                        .referendum_info_for(referendum_index);
+                    let referendum_info = api.storage().at(block_hash).fetch(&query).await?;
+                    Ok(
+                        referendum_info.map(|referendum_info| match referendum_info {
+                            ReferendumInfoFor::Ongoing(_) => ReferendumOutcome::Ongoing,
+                            ReferendumInfoFor::Approved(end, ..) => ReferendumOutcome::Completed {
+                                end,
+                                approved: true,
+                            },
+                            ReferendumInfoFor::Rejected(end, ..) => ReferendumOutcome::Completed {
+                                end,
+                                approved: false,
+                            },
+                            _ => ReferendumOutcome::Ended,
+                        }),
+                    )
+                }
+            }
+        }
+    };
+}
+
+codegen_runtime!(
+    polkadot,
+    PolkadotRuntime,
+    polkadot,
+    asset_hub_polkadot_runtime,
+    "../../../_metadata/polkadot-metadata.scale",
+    "Polkadot Asset Hub, generated from `_metadata/polkadot-metadata.scale`.",
+);
+
+codegen_runtime!(
+    kusama,
+    KusamaRuntime,
+    kusama,
+    asset_hub_kusama_runtime,
+    "../../../_metadata/kusama-metadata.scale",
+    "Kusama Asset Hub, generated from `_metadata/kusama-metadata.scale`.",
+);
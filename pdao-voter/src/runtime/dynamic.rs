@@ -0,0 +1,464 @@
+use crate::runtime::{
+    stored_vote_to_conviction_vote, BoxedPayload, Casting, ReferendumOutcome, Runtime,
+};
+use crate::unlock::ExpiredVote;
+use async_trait::async_trait;
+use pdao_types::governance::{AccountVote, ConvictionVote, OnChainVote};
+use pdao_types::substrate::referendum::ReferendumLookup;
+use std::str::FromStr;
+use subxt::dynamic::{At, Value};
+use subxt::ext::scale_value::ValueDef;
+use subxt::utils::{AccountId32, H256};
+use subxt::{OnlineClient, PolkadotConfig};
+
+const REFERENDA_PALLET: &str = "Referenda";
+
+/// Runtime whose calls and storage are resolved against the metadata fetched on connection
+/// instead of generated code, for the networks we don't keep metadata for.
+pub(crate) struct DynamicRuntime;
+
+/// Collects a byte array or vector, possibly wrapped in newtypes like `H256`, from a value.
+pub(crate) fn value_to_bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+    let values: Vec<&Value<T>> = composite.values().collect();
+    if values.len() == 1 {
+        if let Some(bytes) = value_to_bytes(values[0]) {
+            return Some(bytes);
+        }
+    }
+    values
+        .iter()
+        .map(|value| value.as_u128().and_then(|byte| u8::try_from(byte).ok()))
+        .collect()
+}
+
+/// Reads a number, possibly wrapped in newtypes like `Vote`, from a value.
+pub(crate) fn value_to_u128<T>(value: &Value<T>) -> Option<u128> {
+    if let Some(number) = value.as_u128() {
+        return Some(number);
+    }
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+    let values: Vec<&Value<T>> = composite.values().collect();
+    if values.len() == 1 {
+        value_to_u128(values[0])
+    } else {
+        None
+    }
+}
+
+pub(crate) fn value_to_conviction_vote<T>(value: &Value<T>) -> Option<ConvictionVote> {
+    let ValueDef::Variant(account_vote) = &value.value else {
+        return None;
+    };
+    let amount = |name: &str| account_vote.at(name).and_then(value_to_u128);
+    match account_vote.name.as_str() {
+        "Standard" => Some(stored_vote_to_conviction_vote(
+            u8::try_from(amount("vote")?).ok()?,
+            amount("balance")?,
+        )),
+        "Split" => Some(ConvictionVote::Split {
+            aye: amount("aye")?,
+            nay: amount("nay")?,
+        }),
+        "SplitAbstain" => Some(ConvictionVote::SplitAbstain {
+            aye: amount("aye")?,
+            nay: amount("nay")?,
+            abstain: amount("abstain")?,
+        }),
+        _ => None,
+    }
+}
+
+/// Collects the `(poll_index, AccountVote)` entries of the votes of a `Casting` value, looking
+/// through the `BoundedVec` wrapping.
+fn collect_poll_votes<T>(value: &Value<T>, poll_votes: &mut Vec<(u32, ConvictionVote)>) {
+    let ValueDef::Composite(composite) = &value.value else {
+        return;
+    };
+    let values: Vec<&Value<T>> = composite.values().collect();
+    if let [index, account_vote] = values.as_slice() {
+        if let (Some(index), ValueDef::Variant(_)) = (index.as_u128(), &account_vote.value) {
+            if let (Ok(index), Some(conviction_vote)) =
+                (u32::try_from(index), value_to_conviction_vote(account_vote))
+            {
+                poll_votes.push((index, conviction_vote));
+            }
+            return;
+        }
+    }
+    for value in values {
+        collect_poll_votes(value, poll_votes);
+    }
+}
+
+impl DynamicRuntime {
+    fn get_proxy_call(real_account_address: &str, call: Value) -> anyhow::Result<Value> {
+        let real = AccountId32::from_str(real_account_address)?;
+        Ok(Value::unnamed_variant(
+            "Proxy",
+            [Value::named_variant(
+                "proxy",
+                [
+                    (
+                        "real",
+                        Value::unnamed_variant("Id", [Value::from_bytes(real.0)]),
+                    ),
+                    (
+                        "force_proxy_type",
+                        Value::unnamed_variant("None", Vec::<Value>::new()),
+                    ),
+                    ("call", call),
+                ],
+            )],
+        ))
+    }
+
+    fn get_vote_call(
+        referendum_index: u32,
+        vote: OnChainVote,
+        balance: u128,
+        conviction: u8,
+    ) -> anyhow::Result<Value> {
+        let vote = match ConvictionVote::new(vote, balance, conviction) {
+            ConvictionVote::Standard {
+                aye,
+                conviction,
+                balance,
+            } => Value::named_variant(
+                "Standard",
+                [
+                    (
+                        "vote",
+                        Value::u128((conviction + if aye { 128 } else { 0 }) as u128),
+                    ),
+                    ("balance", Value::u128(balance)),
+                ],
+            ),
+            ConvictionVote::Split { aye, nay } => Value::named_variant(
+                "Split",
+                [("aye", Value::u128(aye)), ("nay", Value::u128(nay))],
+            ),
+            ConvictionVote::SplitAbstain { aye, nay, abstain } => Value::named_variant(
+                "SplitAbstain",
+                [
+                    ("aye", Value::u128(aye)),
+                    ("nay", Value::u128(nay)),
+                    ("abstain", Value::u128(abstain)),
+                ],
+            ),
+        };
+        Ok(Value::unnamed_variant(
+            "ConvictionVoting",
+            [Value::named_variant(
+                "vote",
+                [
+                    ("poll_index", Value::u128(referendum_index as u128)),
+                    ("vote", vote),
+                ],
+            )],
+        ))
+    }
+
+    fn get_batch(proxy_calls: Vec<Value>) -> BoxedPayload {
+        Box::new(subxt::dynamic::tx(
+            "Utility",
+            "batch_all",
+            vec![("calls", Value::unnamed_composite(proxy_calls))],
+        ))
+    }
+}
+
+#[async_trait]
+impl Runtime for DynamicRuntime {
+    fn get_vote_batch(
+        &self,
+        referendum_index: u32,
+        account_votes: &[AccountVote],
+        conviction: u8,
+    ) -> anyhow::Result<BoxedPayload> {
+        let mut proxy_calls = Vec::new();
+        for account_vote in account_votes.iter() {
+            proxy_calls.push(Self::get_proxy_call(
+                &account_vote.address,
+                Self::get_vote_call(
+                    referendum_index,
+                    account_vote.vote,
+                    account_vote.balance,
+                    conviction,
+                )?,
+            )?);
+        }
+        Ok(Self::get_batch(proxy_calls))
+    }
+
+    fn get_remove_vote_batch(
+        &self,
+        referendum_index: u32,
+        account_addresses: &[String],
+    ) -> anyhow::Result<BoxedPayload> {
+        let mut proxy_calls = Vec::new();
+        for account_address in account_addresses.iter() {
+            proxy_calls.push(Self::get_proxy_call(
+                account_address,
+                Value::unnamed_variant(
+                    "ConvictionVoting",
+                    [Value::named_variant(
+                        "remove_vote",
+                        [
+                            ("class", Value::unnamed_variant("None", Vec::<Value>::new())),
+                            ("index", Value::u128(referendum_index as u128)),
+                        ],
+                    )],
+                ),
+            )?);
+        }
+        Ok(Self::get_batch(proxy_calls))
+    }
+
+    async fn get_referendum_lookup(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        referendum_index: u32,
+    ) -> anyhow::Result<Option<ReferendumLookup>> {
+        let query = subxt::dynamic::storage(
+            REFERENDA_PALLET,
+            "ReferendumInfoFor",
+            vec![Value::u128(referendum_index as u128)],
+        );
+        let Some(referendum_info) = api.storage().at_latest().await?.fetch(&query).await? else {
+            return Ok(None);
+        };
+        let referendum_info = referendum_info.to_value()?;
+        let ValueDef::Variant(info) = &referendum_info.value else {
+            return Ok(None);
+        };
+        if info.name != "Ongoing" {
+            return Ok(None);
+        }
+        let Some(status) = info.at(0) else {
+            return Ok(None);
+        };
+        let Some(proposal) = status.at("proposal") else {
+            return Ok(None);
+        };
+        let ValueDef::Variant(bounded) = &proposal.value else {
+            return Ok(None);
+        };
+        if bounded.name != "Lookup" {
+            return Ok(None);
+        }
+        let hash = bounded
+            .at("hash")
+            .and_then(value_to_bytes)
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok());
+        let length = bounded
+            .at("len")
+            .and_then(|len| len.as_u128())
+            .and_then(|len| u32::try_from(len).ok());
+        match (hash, length) {
+            (Some(hash), Some(length)) => Ok(Some(ReferendumLookup { hash, length })),
+            _ => Err(anyhow::anyhow!(
+                "Cannot decode the proposal lookup of referendum {referendum_index}."
+            )),
+        }
+    }
+
+    async fn get_preimage(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        lookup: &ReferendumLookup,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        let query = subxt::dynamic::storage(
+            "Preimage",
+            "PreimageFor",
+            vec![Value::unnamed_composite([
+                Value::from_bytes(lookup.hash),
+                Value::u128(lookup.length as u128),
+            ])],
+        );
+        let Some(preimage) = api.storage().at_latest().await?.fetch(&query).await? else {
+            return Ok(None);
+        };
+        let preimage = value_to_bytes(&preimage.to_value()?)
+            .ok_or(anyhow::anyhow!("Cannot decode the preimage."))?;
+        Ok(Some(preimage))
+    }
+
+    fn get_unlock_batch(
+        &self,
+        expired_votes: &[ExpiredVote],
+        unlocks: &[(String, u16)],
+    ) -> anyhow::Result<BoxedPayload> {
+        let mut proxy_calls = Vec::new();
+        for expired_vote in expired_votes.iter() {
+            proxy_calls.push(Self::get_proxy_call(
+                &expired_vote.address,
+                Value::unnamed_variant(
+                    "ConvictionVoting",
+                    [Value::named_variant(
+                        "remove_vote",
+                        [
+                            (
+                                "class",
+                                Value::unnamed_variant(
+                                    "Some",
+                                    [Value::u128(expired_vote.class as u128)],
+                                ),
+                            ),
+                            ("index", Value::u128(expired_vote.referendum_index as u128)),
+                        ],
+                    )],
+                ),
+            )?);
+        }
+        for (account_address, class) in unlocks.iter() {
+            let target = AccountId32::from_str(account_address)?;
+            proxy_calls.push(Self::get_proxy_call(
+                account_address,
+                Value::unnamed_variant(
+                    "ConvictionVoting",
+                    [Value::named_variant(
+                        "unlock",
+                        [
+                            ("class", Value::u128(*class as u128)),
+                            (
+                                "target",
+                                Value::unnamed_variant("Id", [Value::from_bytes(target.0)]),
+                            ),
+                        ],
+                    )],
+                ),
+            )?);
+        }
+        Ok(Self::get_batch(proxy_calls))
+    }
+
+    async fn get_class_locks(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        block_hash: H256,
+        account_address: &str,
+    ) -> anyhow::Result<Vec<u16>> {
+        let account_id = AccountId32::from_str(account_address)?;
+        let query = subxt::dynamic::storage(
+            "ConvictionVoting",
+            "ClassLocksFor",
+            vec![Value::from_bytes(account_id.0)],
+        );
+        let Some(class_locks) = api.storage().at(block_hash).fetch(&query).await? else {
+            return Ok(Vec::new());
+        };
+        let class_locks = class_locks.to_value()?;
+        // a `BoundedVec` of `(class, balance)` tuples
+        let mut classes = Vec::new();
+        let mut pending = vec![&class_locks];
+        while let Some(value) = pending.pop() {
+            let ValueDef::Composite(composite) = &value.value else {
+                continue;
+            };
+            let values: Vec<_> = composite.values().collect();
+            match values.as_slice() {
+                [class, balance] if class.as_u128().is_some() && balance.as_u128().is_some() => {
+                    if let Some(class) = class.as_u128().and_then(|c| u16::try_from(c).ok()) {
+                        classes.push(class);
+                    }
+                }
+                _ => pending.extend(values),
+            }
+        }
+        Ok(classes)
+    }
+
+    async fn get_casting(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        block_hash: H256,
+        account_address: &str,
+        class: u16,
+    ) -> anyhow::Result<Option<Casting>> {
+        let account_id = AccountId32::from_str(account_address)?;
+        let query = subxt::dynamic::storage(
+            "ConvictionVoting",
+            "VotingFor",
+            vec![Value::from_bytes(account_id.0), Value::u128(class as u128)],
+        );
+        let Some(voting) = api.storage().at(block_hash).fetch(&query).await? else {
+            return Ok(None);
+        };
+        let voting = voting.to_value()?;
+        let ValueDef::Variant(voting) = &voting.value else {
+            return Ok(None);
+        };
+        if voting.name != "Casting" {
+            return Ok(None);
+        }
+        let Some(casting) = voting.at(0) else {
+            return Ok(None);
+        };
+        let mut votes = Vec::new();
+        if let Some(casting_votes) = casting.at("votes") {
+            collect_poll_votes(casting_votes, &mut votes);
+        }
+        let prior = casting.at("prior");
+        let prior_block = prior
+            .and_then(|prior| prior.at(0))
+            .and_then(value_to_u128)
+            .and_then(|block| u32::try_from(block).ok())
+            .unwrap_or_default();
+        let prior_balance = prior
+            .and_then(|prior| prior.at(1))
+            .and_then(value_to_u128)
+            .unwrap_or_default();
+        let delegated_balance = casting
+            .at("delegations")
+            .and_then(|delegations| delegations.at("capital"))
+            .and_then(value_to_u128)
+            .unwrap_or_default();
+        Ok(Some(Casting {
+            votes,
+            prior: (prior_block, prior_balance),
+            delegated_balance,
+        }))
+    }
+
+    async fn get_referendum_outcome(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        block_hash: H256,
+        referendum_index: u32,
+    ) -> anyhow::Result<Option<ReferendumOutcome>> {
+        let query = subxt::dynamic::storage(
+            REFERENDA_PALLET,
+            "ReferendumInfoFor",
+            vec![Value::u128(referendum_index as u128)],
+        );
+        let Some(referendum_info) = api.storage().at(block_hash).fetch(&query).await? else {
+            return Ok(None);
+        };
+        let referendum_info = referendum_info.to_value()?;
+        let ValueDef::Variant(info) = &referendum_info.value else {
+            return Ok(None);
+        };
+        let end = info
+            .at(0)
+            .and_then(value_to_u128)
+            .and_then(|end| u32::try_from(end).ok());
+        let outcome = match (info.name.as_str(), end) {
+            ("Ongoing", _) => ReferendumOutcome::Ongoing,
+            ("Approved", Some(end)) => ReferendumOutcome::Completed {
+                end,
+                approved: true,
+            },
+            ("Rejected", Some(end)) => ReferendumOutcome::Completed {
+                end,
+                approved: false,
+            },
+            _ => ReferendumOutcome::Ended,
+        };
+        Ok(Some(outcome))
+    }
+}
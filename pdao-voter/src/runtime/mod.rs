@@ -0,0 +1,129 @@
+use crate::runtime::codegen::kusama::KusamaRuntime;
+use crate::runtime::codegen::polkadot::PolkadotRuntime;
+use crate::runtime::dynamic::DynamicRuntime;
+use crate::unlock::ExpiredVote;
+use async_trait::async_trait;
+use pdao_types::governance::{AccountVote, ConvictionVote};
+use pdao_types::substrate::chain::Chain;
+use pdao_types::substrate::referendum::ReferendumLookup;
+use subxt::metadata::types::Metadata;
+use subxt::tx::Payload;
+use subxt::utils::H256;
+use subxt::{OnlineClient, PolkadotConfig};
+
+pub(crate) mod codegen;
+pub(crate) mod dynamic;
+
+pub(crate) type BoxedPayload = Box<dyn Payload + Send + Sync>;
+
+/// An account's own votes in a class.
+pub(crate) struct Casting {
+    pub(crate) votes: Vec<(u32, ConvictionVote)>,
+    /// Block until which the balance of already removed votes stays locked, and that balance.
+    pub(crate) prior: (u32, u128),
+    /// Balance delegated to the account in the class.
+    pub(crate) delegated_balance: u128,
+}
+
+pub(crate) enum ReferendumOutcome {
+    Ongoing,
+    /// Approved or rejected, the winning side's votes stay locked for their conviction period
+    /// after the end block.
+    Completed {
+        end: u32,
+        approved: bool,
+    },
+    /// Cancelled, timed out or killed, no votes stay locked.
+    Ended,
+}
+
+fn stored_vote_to_conviction_vote(vote: u8, balance: u128) -> ConvictionVote {
+    ConvictionVote::Standard {
+        aye: vote & 128 != 0,
+        conviction: vote & 127,
+        balance,
+    }
+}
+
+/// Calls and storage queries that differ between the runtimes of the networks the voter
+/// supports. Adding a network takes its metadata in `_metadata` and one `codegen_runtime!`
+/// line, or just a [`DynamicRuntime`] when its pallets match one of the existing runtimes.
+#[async_trait]
+pub(crate) trait Runtime: Send + Sync {
+    /// The metadata the calls were generated from, `None` for runtimes that build their calls
+    /// from the live metadata.
+    fn get_compiled_metadata(&self) -> anyhow::Result<Option<Metadata>> {
+        Ok(None)
+    }
+
+    /// `utility.batch_all` of one proxied vote per account.
+    fn get_vote_batch(
+        &self,
+        referendum_index: u32,
+        account_votes: &[AccountVote],
+        conviction: u8,
+    ) -> anyhow::Result<BoxedPayload>;
+
+    /// `utility.batch_all` of one proxied vote removal per account.
+    fn get_remove_vote_batch(
+        &self,
+        referendum_index: u32,
+        account_addresses: &[String],
+    ) -> anyhow::Result<BoxedPayload>;
+
+    async fn get_referendum_lookup(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        referendum_index: u32,
+    ) -> anyhow::Result<Option<ReferendumLookup>>;
+
+    async fn get_preimage(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        lookup: &ReferendumLookup,
+    ) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// `utility.batch_all` of proxied vote removals, followed by proxied unlocks.
+    fn get_unlock_batch(
+        &self,
+        expired_votes: &[ExpiredVote],
+        unlocks: &[(String, u16)],
+    ) -> anyhow::Result<BoxedPayload>;
+
+    /// Classes in which the account has a lock, from `ConvictionVoting::ClassLocksFor`.
+    async fn get_class_locks(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        block_hash: H256,
+        account_address: &str,
+    ) -> anyhow::Result<Vec<u16>>;
+
+    /// The account's votes in the class from `ConvictionVoting::VotingFor`, `None` when it's
+    /// delegating or has never voted in the class.
+    async fn get_casting(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        block_hash: H256,
+        account_address: &str,
+        class: u16,
+    ) -> anyhow::Result<Option<Casting>>;
+
+    async fn get_referendum_outcome(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        block_hash: H256,
+        referendum_index: u32,
+    ) -> anyhow::Result<Option<ReferendumOutcome>>;
+}
+
+pub(crate) fn get_runtime(chain: &Chain) -> anyhow::Result<Box<dyn Runtime>> {
+    match chain.chain.as_str() {
+        "polkadot" => Ok(Box::new(PolkadotRuntime)),
+        "kusama" => Ok(Box::new(KusamaRuntime)),
+        "westend" | "paseo" => Ok(Box::new(DynamicRuntime)),
+        _ => Err(anyhow::anyhow!(
+            "{} is not supported by the voter.",
+            chain.display
+        )),
+    }
+}
@@ -0,0 +1,196 @@
+use crate::decode::decode_call;
+use crate::offline::get_public_key;
+use crate::{DryRun, Voter};
+use pdao_config::NetworkVoterConfig;
+use pdao_keystore::get_proxy_key_name;
+use pdao_substrate_client::SubstrateClient;
+use pdao_types::substrate::account_id::AccountId;
+use pdao_types::substrate::balance::Balance;
+use pdao_types::substrate::chain::Chain;
+use std::str::FromStr;
+use subxt::tx::{Payload, SubmittableTransaction};
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt::{OnlineClient, PolkadotConfig};
+
+type Extrinsic = SubmittableTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+
+impl Voter {
+    /// The configured proxy account address takes precedence, since the proxy key is left out
+    /// of the keystore with offline signing.
+    pub(crate) fn get_proxy_account_id(
+        &self,
+        chain: &Chain,
+        network_config: &NetworkVoterConfig,
+    ) -> anyhow::Result<AccountId> {
+        match &network_config.proxy_account_address {
+            Some(address) => Ok(AccountId::from_str(address)?),
+            None => Ok(self
+                .keystore
+                .get_signer(&get_proxy_key_name(&chain.chain))?
+                .account_id()),
+        }
+    }
+
+    pub(crate) async fn get_substrate_client(
+        &self,
+        chain: &Chain,
+    ) -> anyhow::Result<SubstrateClient> {
+        SubstrateClient::new(
+            &chain.asset_hub_rpc_url,
+            self.config.substrate.connection_timeout_seconds,
+            self.config.substrate.request_timeout_seconds,
+        )
+        .await
+    }
+
+    pub(crate) async fn get_account_balance(
+        substrate_client: &SubstrateClient,
+        chain: &Chain,
+        account_id: &AccountId,
+    ) -> anyhow::Result<Balance> {
+        substrate_client
+            .get_balance(account_id, None)
+            .await?
+            .ok_or(anyhow::anyhow!(
+                "Account {} not found on {}.",
+                account_id.to_ss58_check_with_version(chain.ss58_prefix),
+                chain.display,
+            ))
+    }
+
+    /// The `Balances::ExistentialDeposit` constant, which an account must keep to stay alive.
+    pub(crate) fn get_existential_deposit(
+        api: &OnlineClient<PolkadotConfig>,
+    ) -> anyhow::Result<u128> {
+        api.constants()
+            .at(&subxt::dynamic::constant("Balances", "ExistentialDeposit"))?
+            .to_value()?
+            .as_u128()
+            .ok_or(anyhow::anyhow!("Cannot decode the existential deposit."))
+    }
+
+    /// Signs the call with the proxy account and estimates its fee with `payment_queryInfo`.
+    /// Fails when the live runtime doesn't match the metadata the call was generated from, or
+    /// when the proxy account cannot pay the fee of an extrinsic to be submitted.
+    ///
+    /// With offline signing the signature of an extrinsic to be submitted comes detached from
+    /// an admin, and a dry run is fee-estimated with an empty signature.
+    async fn sign<Call: Payload>(
+        &self,
+        chain: &Chain,
+        substrate_client: &SubstrateClient,
+        network_config: &NetworkVoterConfig,
+        call: &Call,
+        is_dry_run: bool,
+    ) -> anyhow::Result<(Extrinsic, u128)> {
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        // calls generated from stale metadata may encode wrongly after a runtime upgrade
+        let metadata_check = Self::get_metadata_check(chain, &api)?;
+        if metadata_check.has_drift() {
+            anyhow::bail!(
+                "{} runtime {} does not match the compiled metadata, voting is blocked:\n{}",
+                chain.display,
+                metadata_check.spec_version,
+                metadata_check.mismatches.join("\n"),
+            );
+        }
+        let proxy_account_id = self.get_proxy_account_id(chain, network_config)?;
+        let account_id = AccountId32(get_public_key(&proxy_account_id));
+        let mut partial_extrinsic = if self.is_offline_signing() {
+            Self::create_partial_extrinsic(&api, &proxy_account_id, call).await?
+        } else {
+            api.tx()
+                .create_partial(call, &account_id, Default::default())
+                .await?
+        };
+        // an offline extrinsic is fee-estimated with an empty signature before it gets signed
+        let signature = if self.is_offline_signing() {
+            [0; 64]
+        } else {
+            self.keystore
+                .get_signer(&get_proxy_key_name(&chain.chain))?
+                .sign(&partial_extrinsic.signer_payload())
+        };
+        let extrinsic = partial_extrinsic
+            .sign_with_account_and_signature(&account_id, &MultiSignature::Sr25519(signature));
+        let estimated_fee = substrate_client
+            .get_partial_fee(&format!("0x{}", hex::encode(extrinsic.encoded())))
+            .await?;
+        if is_dry_run {
+            return Ok((extrinsic, estimated_fee));
+        }
+        // fail before submission rather than mid-command when the proxy can't pay the fee
+        let proxy_balance =
+            Self::get_account_balance(substrate_client, chain, &proxy_account_id).await?;
+        let available_balance = proxy_balance.spendable(Self::get_existential_deposit(&api)?);
+        if available_balance < estimated_fee {
+            anyhow::bail!(
+                "Proxy account {} has {} available, less than the estimated fee of {}.",
+                proxy_account_id.to_ss58_check_with_version(chain.ss58_prefix),
+                chain.format_balance(available_balance),
+                chain.format_balance(estimated_fee),
+            );
+        }
+        if !self.is_offline_signing() {
+            return Ok((extrinsic, estimated_fee));
+        }
+        // the partial extrinsic is not `Sync`, don't hold a reference to it across awaits
+        let call_data = partial_extrinsic.call_data().to_vec();
+        let signer_payload = partial_extrinsic.signer_payload();
+        let signature = self
+            .sign_offline(chain, &api, &proxy_account_id, &call_data, &signer_payload)
+            .await?;
+        let extrinsic = partial_extrinsic.sign_with_account_and_signature(&account_id, &signature);
+        Ok((extrinsic, estimated_fee))
+    }
+
+    pub(crate) async fn submit<Call: Payload>(
+        &self,
+        chain: &Chain,
+        network_config: &NetworkVoterConfig,
+        call: &Call,
+    ) -> anyhow::Result<(String, u64, u32)> {
+        if self.is_safe_mode() {
+            return Err(anyhow::anyhow!(
+                "Safe mode is on in the {} environment, on-chain submissions are disabled.",
+                self.config.env,
+            ));
+        }
+        let subtrate_client = self.get_substrate_client(chain).await?;
+        let (extrinsic, _) = self
+            .sign(chain, &subtrate_client, network_config, call, false)
+            .await?;
+        let tx_progress = extrinsic.submit_and_watch().await?;
+        let tx_in_block = tx_progress.wait_for_finalized().await?;
+        let block_hash = tx_in_block.block_hash();
+        let block_hash = format!("0x{}", hex::encode(block_hash.0));
+        let events = tx_in_block.wait_for_success().await?;
+        let header = subtrate_client.get_block_header(&block_hash).await?;
+        let block_number = header.get_number()?;
+        let extrinsic_index = events.extrinsic_index();
+        Ok((block_hash, block_number, extrinsic_index))
+    }
+
+    /// Builds and signs the extrinsic without submitting it, so that the fee can be
+    /// estimated.
+    pub(crate) async fn dry_run<Call: Payload>(
+        &self,
+        chain: &Chain,
+        network_config: &NetworkVoterConfig,
+        call: &Call,
+    ) -> anyhow::Result<DryRun> {
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        let call_data = api.tx().call_data(call)?;
+        // decoded from the encoded call, so that a wrong encoding shows in the summary
+        let summary = decode_call(chain, &api.metadata(), &call_data)?.to_lines();
+        let subtrate_client = self.get_substrate_client(chain).await?;
+        let (_, estimated_fee) = self
+            .sign(chain, &subtrate_client, network_config, call, true)
+            .await?;
+        Ok(DryRun {
+            call_hex: format!("0x{}", hex::encode(call_data)),
+            summary,
+            estimated_fee,
+        })
+    }
+}
@@ -0,0 +1,140 @@
+use crate::runtime::{get_runtime, ReferendumOutcome};
+use crate::{DryRun, Voter};
+use pdao_substrate_client::SubstrateClient;
+use pdao_types::governance::ConvictionVote;
+use pdao_types::substrate::chain::Chain;
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// Most calls in one unlock batch, the rest is left for the next run.
+const MAX_UNLOCK_BATCH_CALLS: usize = 64;
+
+/// A voting account's vote on a finished referendum whose conviction lock has expired.
+pub struct ExpiredVote {
+    pub address: String,
+    pub class: u16,
+    pub referendum_index: u32,
+}
+
+/// Expired votes to remove, followed by the account classes to unlock.
+pub struct UnlockPlan {
+    pub expired_votes: Vec<ExpiredVote>,
+    pub unlocks: Vec<(String, u16)>,
+}
+
+impl UnlockPlan {
+    pub fn is_empty(&self) -> bool {
+        self.expired_votes.is_empty() && self.unlocks.is_empty()
+    }
+
+    fn call_count(&self) -> usize {
+        self.expired_votes.len() + self.unlocks.len()
+    }
+}
+
+/// Number of vote locking periods a conviction locks the balance for.
+fn get_lock_periods(conviction: u8) -> u32 {
+    match conviction {
+        0 => 0,
+        conviction => 1 << (conviction.min(6) - 1),
+    }
+}
+
+impl Voter {
+    /// Finds the voting accounts' votes on finished referenda whose locks have expired, and the
+    /// classes to unlock after removing them.
+    pub async fn get_unlock_plan(&self, chain: &Chain) -> anyhow::Result<UnlockPlan> {
+        let runtime = get_runtime(chain)?;
+        let api = OnlineClient::<PolkadotConfig>::from_url(&chain.asset_hub_rpc_url).await?;
+        let block = api.blocks().at_latest().await?;
+        let vote_locking_period = api
+            .constants()
+            .at(&subxt::dynamic::constant(
+                "ConvictionVoting",
+                "VoteLockingPeriod",
+            ))?
+            .to_value()?
+            .as_u128()
+            .and_then(|period| u32::try_from(period).ok())
+            .ok_or(anyhow::anyhow!("Cannot decode the vote locking period."))?;
+        // referenda and conviction locks on the asset hubs are timed in relay chain blocks
+        let relay_client = SubstrateClient::new(
+            &chain.rpc_url,
+            self.config.substrate.connection_timeout_seconds,
+            self.config.substrate.request_timeout_seconds,
+        )
+        .await?;
+        let relay_block_number = relay_client.get_finalized_block_number().await? as u32;
+
+        let mut plan = UnlockPlan {
+            expired_votes: Vec::new(),
+            unlocks: Vec::new(),
+        };
+        for voting_account in self.get_network_config(chain)?.voting_accounts.iter() {
+            let address = &voting_account.address;
+            for class in runtime.get_class_locks(&api, block.hash(), address).await? {
+                let Some(casting) = runtime
+                    .get_casting(&api, block.hash(), address, class)
+                    .await?
+                else {
+                    continue;
+                };
+                let (prior_block, prior_balance) = casting.prior;
+                let mut can_unlock = prior_balance > 0 && prior_block <= relay_block_number;
+                for (referendum_index, conviction_vote) in casting.votes.iter() {
+                    if plan.call_count() + 1 >= MAX_UNLOCK_BATCH_CALLS {
+                        break;
+                    }
+                    let unlocks_at = match runtime
+                        .get_referendum_outcome(&api, block.hash(), *referendum_index)
+                        .await?
+                    {
+                        Some(ReferendumOutcome::Ongoing) => continue,
+                        Some(ReferendumOutcome::Completed { end, approved }) => {
+                            match conviction_vote {
+                                ConvictionVote::Standard {
+                                    aye, conviction, ..
+                                } if *aye == approved => end.saturating_add(
+                                    vote_locking_period
+                                        .saturating_mul(get_lock_periods(*conviction)),
+                                ),
+                                _ => end,
+                            }
+                        }
+                        Some(ReferendumOutcome::Ended) | None => 0,
+                    };
+                    if unlocks_at <= relay_block_number {
+                        plan.expired_votes.push(ExpiredVote {
+                            address: address.clone(),
+                            class,
+                            referendum_index: *referendum_index,
+                        });
+                        can_unlock = true;
+                    }
+                }
+                if can_unlock {
+                    plan.unlocks.push((address.clone(), class));
+                }
+                if plan.call_count() >= MAX_UNLOCK_BATCH_CALLS {
+                    return Ok(plan);
+                }
+            }
+        }
+        Ok(plan)
+    }
+
+    pub async fn unlock(
+        &self,
+        chain: &Chain,
+        plan: &UnlockPlan,
+    ) -> anyhow::Result<(String, u64, u32)> {
+        let call = get_runtime(chain)?.get_unlock_batch(&plan.expired_votes, &plan.unlocks)?;
+        let network_config = self.get_network_config(chain)?;
+        self.submit(chain, &network_config, &call).await
+    }
+
+    pub async fn dry_run_unlock(&self, chain: &Chain, plan: &UnlockPlan) -> anyhow::Result<DryRun> {
+        let call = get_runtime(chain)?.get_unlock_batch(&plan.expired_votes, &plan.unlocks)?;
+        let network_config = self.get_network_config(chain)?;
+        self.dry_run(chain, &network_config, &call).await
+    }
+}